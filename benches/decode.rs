@@ -0,0 +1,152 @@
+//! Decoder benchmarks.
+//!
+//! Run with `cargo bench --bench decode`. Every case builds its file once, keeps a single
+//! `SpudDecoder` around and times `SpudDecoder::decode_values` over it, so only the decoding
+//! itself is measured.
+//!
+//! Expected orders of magnitude with a release build:
+//!
+//! | case          | file                                      | per iteration |
+//! |---------------|-------------------------------------------|---------------|
+//! | field-heavy   | 1 000 objects x 64 distinct fields        | ~10 ms        |
+//! | number-heavy  | 1 object with ~100 000 mixed numbers      | ~10 ms        |
+//! | nested        | 1 000 objects nested 8 levels deep        | ~10 ms        |
+//! | blob-heavy    | 100 objects x 64 KiB blobs                | ~100 ms       |
+//!
+//! A result an order of magnitude above these numbers is a regression worth looking into.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use spud_rs::{
+    SpudBuilderSync, SpudDecoder, SpudError, SpudObjectSync, set_instance_identifier,
+    types::BinaryBlob,
+};
+
+const WARMUP_ITERATIONS: u32 = 3;
+const MEASURED_ITERATIONS: u32 = 20;
+
+fn field_heavy() -> Vec<u8> {
+    let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+    for i in 0..1_000_u32 {
+        builder
+            .object(|obj| {
+                for field in 0..64_u32 {
+                    obj.add_value(&format!("field_{field:02}"), i + field)?;
+                }
+
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    builder.encode().unwrap()
+}
+
+fn number_heavy() -> Vec<u8> {
+    let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+    builder
+        .object(|obj| {
+            obj.array("u64s", |arr| {
+                arr.extend((0..25_000_u64).map(|i| i * 1_000_003))?;
+                Ok(())
+            })?;
+            obj.array("i32s", |arr| {
+                arr.extend(-12_500..12_500_i32)?;
+                Ok(())
+            })?;
+            obj.array("f64s", |arr| {
+                arr.extend((0..25_000_u32).map(|i| f64::from(i) * 0.25))?;
+                Ok(())
+            })?;
+            obj.array("u8s", |arr| {
+                arr.extend((0..=u8::MAX).cycle().take(25_000))?;
+                Ok(())
+            })?;
+
+            Ok(())
+        })
+        .unwrap();
+
+    builder.encode().unwrap()
+}
+
+fn nest(obj: &SpudObjectSync, depth: u8) -> Result<(), SpudError> {
+    obj.add_value("depth", depth)?;
+
+    if depth == 0 {
+        return Ok(());
+    }
+
+    obj.object("child", |child| nest(child, depth - 1))
+}
+
+fn nested() -> Vec<u8> {
+    let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+    for _ in 0..1_000 {
+        builder.object(|obj| nest(obj, 8)).unwrap();
+    }
+
+    builder.encode().unwrap()
+}
+
+fn blob_heavy() -> Vec<u8> {
+    let builder: SpudBuilderSync = SpudBuilderSync::new();
+    let blob: Vec<u8> = (0..251_u8).cycle().take(64 * 1024).collect();
+
+    for _ in 0..100 {
+        builder
+            .object(|obj| {
+                obj.add_value("blob", BinaryBlob::new(&blob))?;
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    builder.encode().unwrap()
+}
+
+fn bench(name: &str, file: &[u8], expected_objects: usize) {
+    let decoder: SpudDecoder = SpudDecoder::new(file).unwrap();
+
+    assert_eq!(decoder.decode_values().unwrap().len(), expected_objects);
+
+    for _ in 0..WARMUP_ITERATIONS {
+        black_box(decoder.decode_values().unwrap());
+    }
+
+    let mut fastest: Duration = Duration::MAX;
+    let mut total: Duration = Duration::ZERO;
+
+    for _ in 0..MEASURED_ITERATIONS {
+        let start: Instant = Instant::now();
+
+        black_box(decoder.decode_values().unwrap());
+
+        let elapsed: Duration = start.elapsed();
+
+        fastest = fastest.min(elapsed);
+        total += elapsed;
+    }
+
+    println!(
+        "{name:<14} {:>10} bytes   mean {:>12.3?}   fastest {:>12.3?}",
+        file.len(),
+        total / MEASURED_ITERATIONS,
+        fastest
+    );
+}
+
+fn main() {
+    set_instance_identifier([0, 0, 0]).unwrap();
+
+    bench("field-heavy", &field_heavy(), 1_000);
+    bench("number-heavy", &number_heavy(), 1);
+    bench("nested", &nested(), 1_000);
+    bench("blob-heavy", &blob_heavy(), 100);
+}
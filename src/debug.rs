@@ -0,0 +1,104 @@
+//! Tools for diagnosing encoder bugs and nondeterministic output, not part of the file format.
+
+use crate::spud_types::SpudTypes;
+
+/// Finds the first offset at which `a` and `b` differ and describes the byte each buffer holds there.
+///
+/// Bytes are described by the type they would be read as, e.g. `"offset 42: a=U8 b=U16"`, falling back to
+/// their hex value when they are not a type byte, and to `end` for the buffer that is a prefix of the other.
+/// The offset is not parsed in context, so a differing payload byte that happens to match a type byte is
+/// still described as that type.
+///
+/// # Examples
+///
+/// ```rust
+/// use spud_rs::debug::first_difference;
+///
+/// assert_eq!(first_difference(&[0x03, 0x09], &[0x03, 0x0A]), Some((1, "offset 1: a=U8 b=U16".to_owned())));
+/// assert_eq!(first_difference(&[0x03], &[0x03]), None);
+/// ```
+///
+/// # Returns
+///
+/// The offset and its description, or `None` if the buffers are identical.
+#[must_use]
+pub fn first_difference(a: &[u8], b: &[u8]) -> Option<(usize, String)> {
+    let offset: usize = match a.iter().zip(b).position(|(a, b)| a != b) {
+        Some(offset) => offset,
+        None if a.len() == b.len() => return None,
+        None => a.len().min(b.len()),
+    };
+
+    Some((
+        offset,
+        format!(
+            "offset {offset}: a={} b={}",
+            describe(a.get(offset).copied()),
+            describe(b.get(offset).copied())
+        ),
+    ))
+}
+
+fn describe(byte: Option<u8>) -> String {
+    match byte {
+        Some(byte) => SpudTypes::from_u8(byte).map_or_else(
+            || format!("{byte:#04X}"),
+            |spud_type| spud_type.name().to_owned(),
+        ),
+        None => "end".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_first_difference() {
+        use crate::{SpudBuilderSync, spud_types::SpudNumberTypes};
+
+        let encode = |value: u16| -> Vec<u8> {
+            let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+            builder
+                .object(|obj| {
+                    obj.add_value("port", value)?;
+
+                    Ok(())
+                })
+                .unwrap();
+
+            builder.encode().unwrap()
+        };
+
+        let a: Vec<u8> = encode(8080);
+        let mut b: Vec<u8> = a.clone();
+
+        let offset: usize = a
+            .windows(2)
+            .position(|w| w == 8080u16.to_le_bytes())
+            .unwrap()
+            - 1;
+
+        b[offset] = SpudTypes::Number(SpudNumberTypes::U8).as_u8();
+
+        assert_eq!(
+            first_difference(&a, &b),
+            Some((offset, format!("offset {offset}: a=U16 b=U8")))
+        );
+    }
+
+    #[test]
+    fn test_first_difference_prefix_and_raw_bytes() {
+        assert_eq!(first_difference(&[], &[]), None);
+        assert_eq!(
+            first_difference(&[0x03], &[0x03, 0x04]),
+            Some((1, "offset 1: a=end b=Bool".to_owned()))
+        );
+        assert_eq!(
+            first_difference(&[0xFF], &[0x12]),
+            Some((0, "offset 0: a=0xFF b=Object".to_owned()))
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use crate::spud_types::{SpudNumberTypes, SpudTypes};
 
 pub(crate) fn add_value_length(data: &mut Vec<u8>, value_len: usize) {
@@ -19,6 +21,8 @@ pub(crate) fn add_value_length(data: &mut Vec<u8>, value_len: usize) {
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec;
+
     use super::*;
 
     #[test]
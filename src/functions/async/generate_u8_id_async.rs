@@ -3,6 +3,12 @@ use crate::SpudError;
 type VecBool<'a> = tokio::sync::MutexGuard<'a, Vec<bool>>;
 
 pub(crate) fn generate_u8_id_async(id_vec: &mut VecBool) -> Result<u8, SpudError> {
+    if !id_vec.contains(&false) {
+        return Err(SpudError::EncodingError(
+            "field name id space exhausted, a file can hold at most 254 distinct names".to_owned(),
+        ));
+    }
+
     let mut id: [u8; 1] = [0_u8; 1];
 
     getrandom::fill(&mut id)?;
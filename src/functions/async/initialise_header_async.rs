@@ -1,24 +1,41 @@
 use indexmap::IndexMap;
 
-use crate::{SPUD_VERSION, spud_types::SpudTypes};
+use crate::{
+    SPUD_VERSION,
+    functions::{write_field_footer, write_field_table},
+    spud_types::SpudTypes,
+    types::Capabilities,
+};
 
 type FieldNames<'a> = tokio::sync::MutexGuard<'a, IndexMap<(String, u8), u8>>;
 
-pub(crate) fn initialise_header_async(field_names: &FieldNames, data: &[u8]) -> Vec<u8> {
-    let mut header: Vec<u8> = SPUD_VERSION.as_bytes().to_vec();
+pub(crate) fn initialise_header_async(
+    field_names: &FieldNames,
+    data: &[u8],
+    capabilities: Capabilities,
+) -> Vec<u8> {
+    let field_names_len: usize = field_names
+        .keys()
+        .map(|(name, _)| name.len() + 2)
+        .sum::<usize>();
 
-    for (name, id) in field_names.iter() {
-        header.push(name.1);
+    let mut header: Vec<u8> =
+        Vec::with_capacity(SPUD_VERSION.len() + field_names_len + 1 + data.len() + 8 + 4);
 
-        header.extend_from_slice(name.0.as_bytes());
+    header.extend_from_slice(SPUD_VERSION.as_bytes());
 
-        header.push(*id);
-    }
+    if capabilities.contains(Capabilities::FOOTER_FIELD_TABLE) {
+        header.push(SpudTypes::FieldNameListEnd.as_u8());
+        header.extend_from_slice(data);
+
+        write_field_footer(field_names, header.len(), &mut header);
+    } else {
+        write_field_table(field_names, &mut header);
 
-    header.push(SpudTypes::FieldNameListEnd.as_u8());
+        header.extend_from_slice(data);
+    }
 
-    header.extend_from_slice(data);
-    header.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+    header.extend_from_slice(&capabilities.as_trailer());
 
     header
 }
@@ -50,7 +67,11 @@ mod tests {
         #[cfg(feature = "async")]
         let field_names = Mutex::new(field_names);
 
-        let header: Vec<u8> = initialise_header_async(&field_names.try_lock().unwrap(), &data);
+        let header: Vec<u8> = initialise_header_async(
+            &field_names.try_lock().unwrap(),
+            &data,
+            Capabilities::empty(),
+        );
 
         assert_eq!(
             header.len(),
@@ -0,0 +1,36 @@
+use indexmap::IndexMap;
+
+use crate::{SpudError, spud_types::SpudTypes};
+
+/// Returns the length byte `name` is stored with in the field-name table.
+///
+/// # Errors
+///
+/// Returns `SpudError::ValidationError` naming the field if it is longer than 255 bytes.
+pub(crate) fn field_name_len(name: &str) -> Result<u8, SpudError> {
+    u8::try_from(name.len())
+        .map_err(|_| SpudError::ValidationError(format!("Field name '{name}' exceeds 255 bytes")))
+}
+
+/// Appends the field-name table to `out`: every name's length, bytes and id, then `FieldNameListEnd`.
+pub(crate) fn write_field_table(field_names: &IndexMap<(String, u8), u8>, out: &mut Vec<u8>) {
+    for ((name, name_len), id) in field_names {
+        out.push(*name_len);
+        out.extend_from_slice(name.as_bytes());
+        out.push(*id);
+    }
+
+    out.push(SpudTypes::FieldNameListEnd.as_u8());
+}
+
+/// Appends the field-name table of a `Capabilities::FOOTER_FIELD_TABLE` file to `out`, followed by
+/// `table_offset`, the position of the table in the file, as a little-endian `u64`.
+pub(crate) fn write_field_footer(
+    field_names: &IndexMap<(String, u8), u8>,
+    table_offset: usize,
+    out: &mut Vec<u8>,
+) {
+    write_field_table(field_names, out);
+
+    out.extend_from_slice(&(table_offset as u64).to_le_bytes());
+}
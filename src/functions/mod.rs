@@ -1,5 +1,14 @@
 mod add_value_len;
+#[cfg(any(feature = "sync", feature = "async"))]
 mod check_path;
+#[cfg(any(feature = "sync", feature = "async"))]
+mod crc32;
+#[cfg(any(feature = "sync", feature = "async"))]
+mod field_table;
+#[cfg(any(feature = "sync", feature = "async"))]
+mod next_u8_id;
+#[cfg(any(feature = "sync", feature = "async"))]
+mod sha256;
 
 #[cfg(feature = "async")]
 mod r#async;
@@ -13,8 +22,16 @@ pub(crate) use r#async::*;
 #[cfg(feature = "sync")]
 pub(crate) use sync::*;
 
-#[cfg(any(feature = "sync", feature = "async"))]
 pub(crate) use add_value_len::add_value_length;
 
 #[cfg(any(feature = "sync", feature = "async"))]
 pub(crate) use check_path::check_path;
+
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) use crc32::crc32;
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) use field_table::{field_name_len, write_field_footer, write_field_table};
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) use next_u8_id::next_u8_id;
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) use sha256::sha256;
@@ -0,0 +1,35 @@
+use crate::SpudError;
+
+/// Hands out the lowest free field id, so ids depend only on the order names are first used.
+pub(crate) fn next_u8_id(id_vec: &mut [bool]) -> Result<u8, SpudError> {
+    let Some(id) = id_vec.iter().position(|seen| !seen) else {
+        return Err(SpudError::EncodingError(
+            "field name id space exhausted, a file can hold at most 254 distinct names".to_owned(),
+        ));
+    };
+
+    id_vec[id] = true;
+
+    Ok(u8::try_from(id)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_u8_id() {
+        let mut id_vec: Vec<bool> = vec![false; 256];
+
+        id_vec[0] = true;
+        id_vec[1] = true;
+        id_vec[3] = true;
+
+        assert_eq!(next_u8_id(&mut id_vec).unwrap(), 2);
+        assert_eq!(next_u8_id(&mut id_vec).unwrap(), 4);
+
+        id_vec.fill(true);
+
+        assert!(next_u8_id(&mut id_vec).is_err());
+    }
+}
@@ -3,6 +3,12 @@ use crate::SpudError;
 type VecBool = Vec<bool>;
 
 pub(crate) fn generate_u8_id_sync(id_vec: &mut VecBool) -> Result<u8, SpudError> {
+    if !id_vec.contains(&false) {
+        return Err(SpudError::EncodingError(
+            "field name id space exhausted, a file can hold at most 254 distinct names".to_owned(),
+        ));
+    }
+
     let mut id: [u8; 1] = [0_u8; 1];
 
     getrandom::fill(&mut id)?;
@@ -45,4 +51,17 @@ mod tests {
             "The generated ID should be marked as used in the tracker"
         );
     }
+
+    #[test]
+    fn test_generate_u8_id_exhausted() {
+        #[cfg(not(feature = "async"))]
+        let mut id_tracker: VecBool = vec![true; 256];
+
+        #[cfg(feature = "async")]
+        let binding: Mutex<Vec<bool>> = Mutex::new(vec![true; 256]);
+        #[cfg(feature = "async")]
+        let mut id_tracker = binding.try_lock().unwrap();
+
+        assert!(generate_u8_id_sync(&mut id_tracker).is_err());
+    }
 }
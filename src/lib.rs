@@ -1,29 +1,48 @@
-#[cfg(all(not(feature = "async"), not(feature = "sync")))]
-compile_error!("You must enable either the 'async' or 'sync' feature (or both)!");
+//! Without the `std` feature, which `sync` and `async` enable, the crate builds on `core` and `alloc` and
+//! only provides the value encoders: `SpudTypesExt`, implemented for the numeric types, `bool`, `char`, `()`,
+//! `&str`, `String`, slices, `Vec`s, ranges, durations, IP addresses and `types::{Date, Time, DateTime, Duration, Decimal}`, along with
+//! `SPUD_VERSION`, `OBJECT_FORMAT_VERSION` and the `SpudTypes` tags to lay out a file around them. Select it with
+//! `default-features = false, features = ["no_std"]`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(not(feature = "async"), not(feature = "sync"), not(feature = "no_std")))]
+compile_error!(
+    "You must enable either the 'async' or 'sync' feature (or both), or 'no_std' for the value encoders only!"
+);
 
-#[cfg(any(feature = "sync", feature = "async"))]
 pub const SPUD_VERSION: &str = "SPUD-0.8.2";
 
+/// The layout version stamped on top-level objects by builders with `with_object_versions` enabled.
+pub const OBJECT_FORMAT_VERSION: u8 = 1;
+
 #[cfg(any(feature = "sync", feature = "async"))]
+pub mod debug;
 pub mod types;
 
-#[cfg(any(feature = "sync", feature = "async"))]
 mod functions;
 
-#[cfg(any(feature = "sync", feature = "async"))]
 mod spud_builder;
 #[cfg(any(feature = "sync", feature = "async"))]
 mod spud_decoder;
-#[cfg(any(feature = "sync", feature = "async"))]
 mod spud_error;
 #[cfg(any(feature = "sync", feature = "async"))]
+mod spud_schema;
 mod spud_types;
 
-#[cfg(any(feature = "sync", feature = "async"))]
 pub use spud_builder::*;
 
 #[cfg(any(feature = "sync", feature = "async"))]
 pub use spud_decoder::*;
 
-#[cfg(any(feature = "sync", feature = "async"))]
 pub use spud_error::SpudError;
+
+pub use spud_types::{SpudNumberTypes, SpudTypes};
+
+#[cfg(any(feature = "sync", feature = "async"))]
+pub use spud_schema::{SpudSchema, SpudSchemaTypes};
+
+#[cfg(any(feature = "sync", feature = "async"))]
+pub use types::set_instance_identifier;
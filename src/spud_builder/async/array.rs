@@ -0,0 +1,59 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::{SpudError, spud_builder::spud_type_ext::SpudTypesExt};
+
+/// Represents a SPUD array that is being built in place.
+/// Every pushed element is written straight into the builder's data buffer, so elements are never retained.
+#[derive(Debug)]
+pub struct SpudArrayAsync {
+    data: Arc<Mutex<Vec<u8>>>,
+}
+
+impl SpudArrayAsync {
+    pub(crate) fn new(data: Arc<Mutex<Vec<u8>>>) -> Self {
+        Self { data }
+    }
+
+    /// Appends a value to the array.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to be appended, which must implement the `SpudTypesExt` trait.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the `SpudArrayAsync`, allowing for method chaining.
+    ///
+    /// # Errors
+    ///
+    /// This method currently never fails, the `Result` mirrors `SpudObjectAsync::add_value`.
+    pub async fn push<T: SpudTypesExt>(&self, value: T) -> Result<&Self, SpudError> {
+        value.write_spud_bytes(&mut *self.data.lock().await);
+
+        Ok(self)
+    }
+
+    /// Appends every value yielded by an iterator to the array, without collecting it first.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - An iterator of values, each of which must implement the `SpudTypesExt` trait.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the values cannot be pushed.
+    pub async fn extend<T: SpudTypesExt, I: IntoIterator<Item = T>>(
+        &self,
+        values: I,
+    ) -> Result<&Self, SpudError> {
+        for value in values {
+            self.push(value).await?;
+        }
+
+        Ok(self)
+    }
+}
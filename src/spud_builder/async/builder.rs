@@ -5,19 +5,35 @@ use std::{fmt, future::Future, path::Path, sync::Arc};
 use tokio::sync::{Mutex, MutexGuard};
 
 use crate::{
-    SpudError,
-    functions::{check_path, initialise_header_async},
+    OBJECT_FORMAT_VERSION, SPUD_VERSION, SpudError,
+    functions::{check_path, crc32, initialise_header_async},
+    spud_builder::Nesting,
     spud_types::SpudTypes,
-    types::ObjectId,
+    types::{Capabilities, DateTime, ObjectId},
 };
 
-use tokio::fs::write;
+use tokio::{
+    fs::{File, write},
+    io::{AsyncSeekExt, AsyncWriteExt},
+};
 
 use super::SpudObjectAsync;
 
 #[derive(Default, Clone)]
 pub(crate) struct ObjectMap(pub(crate) IndexMap<ObjectId, Arc<Mutex<SpudObjectAsync>>>);
 
+impl ObjectMap {
+    /// Empties the map, releasing every object in it so none is kept alive by a reference cycle.
+    async fn clear(&mut self) {
+        for (_, object) in self.0.drain(..) {
+            object.lock().await.release().await;
+        }
+    }
+}
+
+/// Position of the closing `ArrayEnd` byte of every array opened through `append_to_array`, keyed by owning object and field name.
+pub(crate) type OpenArrays = IndexMap<(ObjectId, String), usize>;
+
 #[derive(Default, Clone)]
 /// Represents a builder for creating SPUD objects.
 ///
@@ -32,6 +48,12 @@ pub struct SpudBuilderAsync {
     pub(crate) data: Arc<Mutex<Vec<u8>>>,
     pub(crate) objects: Arc<Mutex<ObjectMap>>,
     pub(crate) seen_ids: Arc<Mutex<Vec<bool>>>,
+    pub(crate) open_arrays: Arc<Mutex<OpenArrays>>,
+    auto_timestamp: Option<String>,
+    max_depth: Option<usize>,
+    unique_fields: bool,
+    deterministic: bool,
+    capabilities: Capabilities,
 }
 
 impl SpudBuilderAsync {
@@ -60,9 +82,158 @@ impl SpudBuilderAsync {
             data: Arc::new(Mutex::new(Vec::new())),
             objects: Arc::new(Mutex::new(ObjectMap(IndexMap::new()))),
             seen_ids: Arc::new(Mutex::new(seen_ids)),
+            open_arrays: Arc::new(Mutex::new(IndexMap::new())),
+            auto_timestamp: None,
+            max_depth: None,
+            unique_fields: false,
+            deterministic: false,
+            capabilities: Capabilities::empty(),
+        }
+    }
+
+    #[must_use]
+    /// Creates a new `SpudBuilderAsync` whose output depends only on what is added to it, not on chance.
+    ///
+    /// Field names are handed the lowest free id in the order they are first used, instead of a random one,
+    /// and `encode` writes the field-name table sorted by name. Object ids are still generated from the clock,
+    /// so two files built at different times differ in those 10 bytes per object.
+    ///
+    /// # Tradeoffs
+    ///
+    /// Objects can still be written out as they are finished, but the sorted table can only be written
+    /// once every name is known: either after the last object, or after the objects with `with_footer_field_table`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `SpudBuilderAsync` with deterministic field ids.
+    pub fn new_deterministic() -> Self {
+        Self {
+            deterministic: true,
+            ..Self::new()
         }
     }
 
+    #[must_use]
+    /// Creates a new `SpudBuilderAsync` whose data buffer can hold `bytes` bytes of encoded objects before it reallocates.
+    ///
+    /// Every value is appended to one shared buffer, so when the size of the output is roughly known up front,
+    /// reserving it here avoids the repeated reallocation and copying of a buffer that grows from empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The number of bytes to reserve, it does not limit how much can be written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderAsync;
+    ///
+    /// let builder = SpudBuilderAsync::with_capacity(64 * 1024);
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `SpudBuilderAsync` with a pre-allocated data buffer.
+    pub fn with_capacity(bytes: usize) -> Self {
+        Self {
+            data: Arc::new(Mutex::new(Vec::with_capacity(bytes))),
+            ..Self::new()
+        }
+    }
+
+    /// Stamps every top-level object with the time it was created, stored as a `DateTime` in UTC.
+    ///
+    /// The timestamp is written as the object's first field, nested objects are not stamped.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field holding the timestamp, e.g. `created_at`.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudBuilderAsync`, allowing for method chaining.
+    pub fn with_auto_timestamp(&mut self, field_name: &str) -> &mut Self {
+        self.auto_timestamp = Some(field_name.to_owned());
+
+        self
+    }
+
+    /// Limits how deeply objects can be nested, top-level objects being at depth 1.
+    ///
+    /// A nested `object()` call that would go past the limit returns
+    /// `SpudError::EncodingError("max nesting depth exceeded")` without writing anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_depth` - The deepest level an object can be created at.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudBuilderAsync`, allowing for method chaining.
+    pub fn with_max_depth(&mut self, max_depth: usize) -> &mut Self {
+        self.max_depth = Some(max_depth);
+
+        self
+    }
+
+    /// Rejects writing the same field twice on one object, which would otherwise keep both entries
+    /// and let the last one win on decode.
+    ///
+    /// A second `add_value`, `object`, `array` or other field write with a name the object already holds
+    /// returns `SpudError::ValidationError`. Extending an array with `append_to_array` is not a duplicate.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudBuilderAsync`, allowing for method chaining.
+    pub fn with_unique_fields(&mut self) -> &mut Self {
+        self.unique_fields = true;
+
+        self
+    }
+
+    /// Follows every top-level object with a CRC-32 of its bytes, so the decoder can tell which objects were corrupted.
+    ///
+    /// The checksum is written as a little-endian `u32` right after the object's `ObjectEnd ObjectEnd` bytes,
+    /// and the file declares `Capabilities::CHECKSUM` in its trailer. Nested objects are covered by their top-level object.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudBuilderAsync`, allowing for method chaining.
+    pub fn with_object_checksums(&mut self) -> &mut Self {
+        self.capabilities.insert(Capabilities::CHECKSUM);
+
+        self
+    }
+
+    /// Stamps every top-level object with the per-object format version, `OBJECT_FORMAT_VERSION`.
+    ///
+    /// The version is written as one byte right after the object's id, and the file declares
+    /// `Capabilities::OBJECT_VERSION` in its trailer. Nested objects share the version of their top-level object.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudBuilderAsync`, allowing for method chaining.
+    pub fn with_object_versions(&mut self) -> &mut Self {
+        self.capabilities.insert(Capabilities::OBJECT_VERSION);
+
+        self
+    }
+
+    /// Writes the field-name table after the objects instead of before them.
+    ///
+    /// The header keeps an empty table, the real one follows the last object together with its position in the
+    /// file as a little-endian `u64`, and the file declares `Capabilities::FOOTER_FIELD_TABLE` in its trailer.
+    /// Decoders that predate the flag reject such files instead of reading them without field names.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudBuilderAsync`, allowing for method chaining.
+    pub fn with_footer_field_table(&mut self) -> &mut Self {
+        self.capabilities.insert(Capabilities::FOOTER_FIELD_TABLE);
+
+        self
+    }
+
     /// Creates a new `SpudObjectAsync` instance associated with this builder.
     ///
     /// # Arguments
@@ -104,24 +275,119 @@ impl SpudBuilderAsync {
         F: FnOnce(Arc<Mutex<SpudObjectAsync>>) -> Fut,
         Fut: Future<Output = Result<(), SpudError>>,
     {
-        let obj: Arc<Mutex<SpudObjectAsync>> = self.new_object().await?;
+        self.object_with_id(ObjectId::new()?, f).await
+    }
+
+    /// Creates a top-level object like `object`, but with a caller-chosen id instead of a freshly generated one.
+    ///
+    /// Useful when re-encoding records that already have stable ids, e.g. from a database, so consumers
+    /// can keep correlating them. The decoder reports the id as the object's `oid`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SpudError::ValidationError` if the builder already holds a top-level object with the same id,
+    /// or an error if the object cannot be created or the closure fails.
+    pub async fn object_with_id<F, Fut>(&self, oid: ObjectId, f: F) -> Result<(), SpudError>
+    where
+        F: FnOnce(Arc<Mutex<SpudObjectAsync>>) -> Fut,
+        Fut: Future<Output = Result<(), SpudError>>,
+    {
+        let start: usize = self.data.lock().await.len();
+
+        let obj: Arc<Mutex<SpudObjectAsync>> = self.new_object(oid).await?;
+
+        f(Arc::clone(&obj)).await?;
+
+        {
+            let mut data: MutexGuard<'_, Vec<u8>> = self.data.lock().await;
+
+            data.push(SpudTypes::ObjectEnd.as_u8());
+            data.push(SpudTypes::ObjectEnd.as_u8());
+
+            if self.capabilities.contains(Capabilities::CHECKSUM) {
+                let checksum: u32 = crc32(&data[start..]);
 
-        f(obj).await?;
+                data.extend_from_slice(&checksum.to_le_bytes());
+            }
+        }
 
-        self.data.lock().await.push(SpudTypes::ObjectEnd.as_u8());
-        self.data.lock().await.push(SpudTypes::ObjectEnd.as_u8());
+        obj.lock().await.close();
 
         Ok(())
     }
 
-    async fn new_object(&self) -> Result<Arc<Mutex<SpudObjectAsync>>, SpudError> {
-        SpudObjectAsync::new(
+    /// Empties the builder so it can produce another, unrelated file, keeping its settings and allocations.
+    ///
+    /// The data buffer, field-name table, objects and id bookkeeping are reset to the state of a new builder,
+    /// while options such as `with_object_checksums` or `with_max_depth` stay in effect. Every object is closed
+    /// and dropped, so a handle retained from before the call rejects further writes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::{SpudBuilderAsync, SpudObjectAsync};
+    /// use tokio::sync::MutexGuard;
+    ///
+    /// async fn foo() -> Result<(), spud_rs::SpudError> {
+    ///     let mut builder = SpudBuilderAsync::new();
+    ///
+    ///     for batch in 0..3u8 {
+    ///         builder.object(async |obj| {
+    ///             let locked_obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+    ///
+    ///             locked_obj.add_value("batch", batch).await?;
+    ///
+    ///             Ok(())
+    ///         }).await?;
+    ///
+    ///         let bytes: Vec<u8> = builder.encode().await?;
+    ///
+    ///         builder.clear().await;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn clear(&mut self) {
+        self.objects.lock().await.clear().await;
+        self.data.lock().await.clear();
+        self.field_names.lock().await.clear();
+        self.open_arrays.lock().await.clear();
+
+        let mut seen_ids: MutexGuard<'_, Vec<bool>> = self.seen_ids.lock().await;
+
+        seen_ids.fill(false);
+        seen_ids[0] = true;
+        seen_ids[1] = true;
+    }
+
+    async fn new_object(&self, oid: ObjectId) -> Result<Arc<Mutex<SpudObjectAsync>>, SpudError> {
+        let object: Arc<Mutex<SpudObjectAsync>> = SpudObjectAsync::new(
+            oid,
             Arc::clone(&self.field_names),
             Arc::clone(&self.seen_ids),
             Arc::clone(&self.objects),
             Arc::clone(&self.data),
+            Arc::clone(&self.open_arrays),
+            self.unique_fields,
+            self.deterministic,
+            Nesting::top_level(self.max_depth),
         )
-        .await
+        .await?;
+
+        if self.capabilities.contains(Capabilities::OBJECT_VERSION) {
+            self.data.lock().await.push(OBJECT_FORMAT_VERSION);
+        }
+
+        if let Some(field_name) = &self.auto_timestamp {
+            object
+                .lock()
+                .await
+                .add_value(field_name, DateTime::now()?)
+                .await?;
+        }
+
+        Ok(object)
     }
 
     /// Encodes all objects associated with this builder into a byte vector.
@@ -160,8 +426,15 @@ impl SpudBuilderAsync {
             object.lock().await.encode().await?;
         }
 
-        let header: Vec<u8> =
-            initialise_header_async(&self.field_names.lock().await, &self.data.lock().await);
+        if self.deterministic {
+            self.field_names.lock().await.sort_keys();
+        }
+
+        let header: Vec<u8> = initialise_header_async(
+            &self.field_names.lock().await,
+            &self.data.lock().await,
+            self.capabilities,
+        );
 
         self.data.lock().await.clear();
         self.data.lock().await.extend_from_slice(&header);
@@ -169,6 +442,44 @@ impl SpudBuilderAsync {
         Ok(header)
     }
 
+    /// Encodes all objects and returns the complete file, header and trailer included, without touching the filesystem.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::{SpudBuilderAsync, SpudObjectAsync};
+    /// use tokio::sync::MutexGuard;
+    ///
+    /// async fn foo() -> Result<(), spud_rs::SpudError> {
+    ///     let mut builder = SpudBuilderAsync::new();
+    ///
+    ///     builder.object(async |obj| {
+    ///         let locked_obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+    ///
+    ///         locked_obj.add_value("val", 1u8).await?;
+    ///
+    ///         Ok(())
+    ///     }).await?;
+    ///
+    ///     let bytes: Vec<u8> = builder.build_bytes().await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the objects cannot be encoded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub async fn build_bytes(&mut self) -> Result<Vec<u8>, SpudError> {
+        self.encode().await?;
+
+        Ok(self.data.lock().await.clone())
+    }
+
     /// Builds the SPUD file at the specified path with the given file name.
     ///
     ///  # Arguments
@@ -217,6 +528,35 @@ impl SpudBuilderAsync {
 
         Ok(())
     }
+
+    /// Replaces the contents of an already open file with the builder's data.
+    ///
+    /// The file is rewound and truncated before writing, so the same handle can be reused for
+    /// periodic snapshots without reopening the file each time. The builder must have been encoded first.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - A file opened for writing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EncodingError` if the builder has not been encoded yet, or an error if the file cannot be rewound, truncated or written to.
+    pub async fn write_to_file(&self, file: &mut File) -> Result<(), SpudError> {
+        let data: MutexGuard<'_, Vec<u8>> = self.data.lock().await;
+
+        if !data.starts_with(SPUD_VERSION.as_bytes()) {
+            return Err(SpudError::EncodingError(
+                "the builder has not been encoded, call encode() before write_to_file()".to_owned(),
+            ));
+        }
+
+        file.seek(std::io::SeekFrom::Start(0)).await?;
+        file.set_len(0).await?;
+        file.write_all(&data).await?;
+        file.flush().await?;
+
+        Ok(())
+    }
 }
 
 impl fmt::Debug for SpudBuilderAsync {
@@ -264,6 +604,20 @@ impl fmt::Debug for SpudBuilderAsync {
 
         debug_builder.field("seen_ids", &seen_ids_to_display);
 
+        let open_arrays: MutexGuard<'_, OpenArrays> = if let Ok(guard) = self.open_arrays.try_lock()
+        {
+            guard
+        } else {
+            return Err(fmt::Error);
+        };
+
+        debug_builder.field("open_arrays", &*open_arrays);
+        debug_builder.field("auto_timestamp", &self.auto_timestamp);
+        debug_builder.field("max_depth", &self.max_depth);
+        debug_builder.field("unique_fields", &self.unique_fields);
+        debug_builder.field("deterministic", &self.deterministic);
+        debug_builder.field("capabilities", &self.capabilities);
+
         debug_builder.finish()
     }
 }
@@ -1,8 +1,12 @@
+mod array;
 mod builder;
 mod object;
+mod object_array;
 
+pub use array::SpudArrayAsync;
 pub use builder::SpudBuilderAsync;
 pub use object::SpudObjectAsync;
+pub use object_array::SpudObjectArrayAsync;
 
 #[cfg(all(test, feature = "async"))]
 mod tests {
@@ -28,6 +32,37 @@ mod tests {
         assert_eq!(builder.seen_ids.lock().await.len(), 256);
     }
 
+    #[tokio::test]
+    async fn test_spud_object_add_value_checked() {
+        use crate::{SpudError, SpudSchema, SpudSchemaTypes};
+
+        let mut schema: SpudSchema = SpudSchema::new();
+
+        schema.add_field("name", SpudSchemaTypes::String);
+
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.add_value_checked(&schema, "name", "Alice").await?;
+
+                assert!(matches!(
+                    obj.add_value_checked(&schema, "name", true).await,
+                    Err(SpudError::ValidationError(_))
+                ));
+                assert!(matches!(
+                    obj.add_value_checked(&schema, "age", 30u8).await,
+                    Err(SpudError::ValidationError(_))
+                ));
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn test_spud_builder_object_empty() {
         let builder: SpudBuilderAsync = SpudBuilderAsync::new();
@@ -873,4 +908,463 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_spud_builder_write_to_file_replaces_contents() {
+        use tokio::fs::{self, File, OpenOptions};
+
+        let first: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        first
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                obj.lock()
+                    .await
+                    .add_value("long_field_name", SpudString::from("a fairly long value"))
+                    .await?;
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        first.encode().await.unwrap();
+
+        let second: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        second
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                obj.lock().await.add_value("a", 1u8).await?;
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let second_bytes: Vec<u8> = second.encode().await.unwrap();
+
+        fs::create_dir_all("./.tmp/spud").await.unwrap();
+
+        let path: &str = "./.tmp/spud/async_write_to_file.spud";
+
+        let mut file: File = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(path)
+            .await
+            .unwrap();
+
+        first.write_to_file(&mut file).await.unwrap();
+        second.write_to_file(&mut file).await.unwrap();
+
+        assert_eq!(fs::read(path).await.unwrap(), second_bytes);
+    }
+
+    #[tokio::test]
+    async fn test_spud_builder_write_to_file_requires_encode() {
+        use tokio::fs::{self, File};
+
+        use crate::SpudError;
+
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.add_value("value", 1u8).await?;
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        fs::create_dir_all("./.tmp/spud").await.unwrap();
+
+        let path: &str = "./.tmp/spud/async_write_to_file_unencoded.spud";
+
+        let mut file: File = File::create(path).await.unwrap();
+
+        assert!(matches!(
+            builder.write_to_file(&mut file).await,
+            Err(SpudError::EncodingError(_))
+        ));
+        assert!(fs::read(path).await.unwrap().is_empty());
+
+        builder.encode().await.unwrap();
+
+        assert!(builder.write_to_file(&mut file).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_spud_builder_auto_timestamp() {
+        use chrono::{NaiveDateTime, Utc};
+
+        use crate::{SpudDecoder, types::DateTime};
+
+        let mut builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder.with_auto_timestamp("created_at");
+
+        for i in 0..2_u8 {
+            builder
+                .object(async move |obj: Arc<Mutex<SpudObjectAsync>>| {
+                    obj.lock().await.add_value("index", i).await?;
+
+                    Ok(())
+                })
+                .await
+                .unwrap();
+        }
+
+        let encoded_bytes: Vec<u8> = builder.encode().await.unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(objects.len(), 2);
+
+        for object in &objects {
+            let created_at: NaiveDateTime = NaiveDateTime::try_from(
+                DateTime::from_str(object["created_at"].as_str().unwrap()).unwrap(),
+            )
+            .unwrap();
+
+            let age: chrono::TimeDelta = Utc::now().naive_utc() - created_at;
+
+            assert!(age >= chrono::TimeDelta::zero() && age < chrono::TimeDelta::minutes(1));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spud_builder_add_value_after_close() {
+        use crate::SpudError;
+
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        let captured: Arc<Mutex<Option<Arc<Mutex<SpudObjectAsync>>>>> = Arc::new(Mutex::new(None));
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                obj.lock().await.add_value("a", 1u8).await?;
+
+                *captured.lock().await = Some(Arc::clone(&obj));
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let handle: Arc<Mutex<SpudObjectAsync>> = captured.lock().await.take().unwrap();
+
+        let Err(SpudError::EncodingError(message)) =
+            handle.lock().await.add_value("b", 2u8).await.map(|_| ())
+        else {
+            panic!("expected an encoding error");
+        };
+
+        assert_eq!(message, "object already closed");
+    }
+
+    #[tokio::test]
+    async fn test_spud_builder_max_depth_exceeded() {
+        use crate::SpudError;
+
+        let mut builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder.with_max_depth(2);
+
+        let result: Result<(), SpudError> = builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                obj.lock()
+                    .await
+                    .object("child", async |child: Arc<Mutex<SpudObjectAsync>>| {
+                        let child: MutexGuard<'_, SpudObjectAsync> = child.lock().await;
+
+                        child.add_value("a", 1u8).await?;
+
+                        child.object("grandchild", async |_| Ok(())).await
+                    })
+                    .await
+            })
+            .await;
+
+        let Err(SpudError::EncodingError(message)) = result else {
+            panic!("expected an encoding error");
+        };
+
+        assert_eq!(message, "max nesting depth exceeded");
+    }
+
+    #[tokio::test]
+    async fn test_spud_builder_build_bytes() {
+        use crate::SpudDecoder;
+
+        let mut builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                obj.lock().await.add_value("val", 1u8).await?;
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let bytes: Vec<u8> = builder.build_bytes().await.unwrap();
+
+        assert!(bytes.ends_with(&[0xDE, 0xAD, 0xBE, 0xEF]));
+
+        let decoder: SpudDecoder = SpudDecoder::new(&bytes).unwrap();
+
+        assert_eq!(decoder.decode_values().unwrap()[0]["val"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_spud_builder_object_array() {
+        use crate::{SpudDecoder, SpudObjectArrayAsync};
+
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.add_object_array(
+                    "users",
+                    async |arr: Arc<Mutex<SpudObjectArrayAsync<'_>>>| {
+                        let arr: MutexGuard<'_, SpudObjectArrayAsync<'_>> = arr.lock().await;
+
+                        arr.push_object(async |user: Arc<Mutex<SpudObjectAsync>>| {
+                            user.lock().await.add_value("name", "Alice").await?;
+
+                            Ok(())
+                        })
+                        .await?;
+                        arr.push_object(async |user: Arc<Mutex<SpudObjectAsync>>| {
+                            user.lock().await.add_value("name", "Bob").await?;
+
+                            Ok(())
+                        })
+                        .await?;
+
+                        Ok(())
+                    },
+                )
+                .await?;
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().await.unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let object: serde_json::Value = decoder.decode_values().unwrap().remove(0);
+
+        let users: &Vec<serde_json::Value> = object["users"].as_array().unwrap();
+
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0]["name"], "Alice");
+        assert_eq!(users[1]["name"], "Bob");
+    }
+
+    #[tokio::test]
+    async fn test_spud_builder_unique_fields() {
+        use crate::SpudError;
+
+        let mut builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder.with_unique_fields();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.add_value("x", 1u8).await?;
+                obj.object("nested", async |nested: Arc<Mutex<SpudObjectAsync>>| {
+                    nested.lock().await.add_value("x", 2u8).await?;
+
+                    Ok(())
+                })
+                .await?;
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let result: Result<(), SpudError> = builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.add_value("x", 1u8).await?;
+                obj.add_value("x", 2u8).await?;
+
+                Ok(())
+            })
+            .await;
+
+        let Err(SpudError::ValidationError(message)) = result else {
+            panic!("expected a validation error");
+        };
+
+        assert_eq!(message, "field 'x' is already set on this object");
+    }
+
+    #[tokio::test]
+    async fn test_spud_builder_map() {
+        use indexmap::IndexMap;
+
+        use crate::SpudDecoder;
+
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        let mut scores: IndexMap<String, u32> = IndexMap::new();
+
+        scores.insert("alice".to_owned(), 42);
+        scores.insert("bob".to_owned(), 7);
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.add_map("scores", &scores).await?;
+                obj.add_value("alice", "not a score").await?;
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().await.unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(objects[0]["scores"]["alice"], 42);
+        assert_eq!(objects[0]["scores"]["bob"], 7);
+        assert_eq!(objects[0]["alice"], "not a score");
+    }
+
+    #[tokio::test]
+    async fn test_spud_builder_deterministic() {
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new_deterministic();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.add_value("zeta", 1u8).await?;
+                obj.add_value("alpha", "a").await?;
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        builder.encode().await.unwrap();
+
+        let field_names: Vec<(String, u8)> = builder
+            .field_names
+            .lock()
+            .await
+            .iter()
+            .map(|((name, _), id)| (name.clone(), *id))
+            .collect();
+
+        assert_eq!(
+            field_names,
+            [("alpha".to_owned(), 3), ("zeta".to_owned(), 2)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spud_builder_object_with_id() {
+        use crate::{SpudDecoder, SpudError, types::ObjectId};
+
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        let oid: ObjectId = ObjectId::from([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        builder
+            .object_with_id(oid, async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                obj.lock().await.add_value("value", 1u8).await?;
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            builder.object_with_id(oid, async |_| Ok(())).await,
+            Err(SpudError::ValidationError(_))
+        ));
+
+        let encoded_bytes: Vec<u8> = builder.encode().await.unwrap();
+
+        assert_eq!(
+            SpudDecoder::new(&encoded_bytes)
+                .unwrap()
+                .object_ids()
+                .unwrap(),
+            [oid]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spud_builder_clear() {
+        use crate::SpudDecoder;
+
+        let mut builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.add_value("first", 1u8).await?;
+                obj.object("nested", async |nested: Arc<Mutex<SpudObjectAsync>>| {
+                    nested.lock().await.add_value("value", 2u8).await?;
+
+                    Ok(())
+                })
+                .await?;
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        builder.encode().await.unwrap();
+
+        builder.clear().await;
+
+        assert_eq!(Arc::strong_count(&builder.data), 1);
+        assert_eq!(Arc::strong_count(&builder.objects), 1);
+        assert!(builder.field_names.lock().await.is_empty());
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                obj.lock().await.add_value("second", 3u8).await?;
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().await.unwrap();
+
+        let objects: Vec<serde_json::Value> = SpudDecoder::new(&encoded_bytes)
+            .unwrap()
+            .decode_values()
+            .unwrap();
+
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0]["second"], 3);
+    }
 }
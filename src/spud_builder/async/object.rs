@@ -6,43 +6,72 @@ use std::{pin::Pin, sync::Arc};
 use tokio::sync::{Mutex, MutexGuard};
 
 use crate::{
-    SpudError, functions::generate_u8_id_async, spud_builder::spud_type_ext::SpudTypesExt,
-    spud_types::SpudTypes, types::ObjectId,
+    SpudError, SpudSchema,
+    functions::{field_name_len, generate_u8_id_async, next_u8_id},
+    spud_builder::{Nesting, spud_type_ext::SpudTypesExt},
+    spud_types::SpudTypes,
+    types::ObjectId,
 };
 
-use super::builder::ObjectMap;
+use super::{
+    SpudArrayAsync, SpudObjectArrayAsync,
+    builder::{ObjectMap, OpenArrays},
+};
 
 /// Represents a SPUD object, which is a collection of fields and values.
 /// It allows adding values to fields and manages the internal data structure for SPUD encoding.
 #[derive(Debug)]
 pub struct SpudObjectAsync {
-    pub(crate) _oid: ObjectId,
+    pub(crate) oid: ObjectId,
     data: Arc<Mutex<Vec<u8>>>,
     field_names: Arc<Mutex<IndexMap<(String, u8), u8>>>,
     seen_ids: Arc<Mutex<Vec<bool>>>,
     objects: Arc<Mutex<ObjectMap>>,
+    open_arrays: Arc<Mutex<OpenArrays>>,
+    /// The ids of the fields written so far, tracked only when the builder rejects duplicate fields.
+    written_fields: Option<Mutex<Vec<u8>>>,
+    /// Whether new field names take the lowest free id instead of a random one.
+    sequential_ids: bool,
+    closed: bool,
+    nesting: Nesting,
 }
 
 impl SpudObjectAsync {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn new(
+        oid: ObjectId,
         field_names: Arc<Mutex<IndexMap<(String, u8), u8>>>,
         seen_ids: Arc<Mutex<Vec<bool>>>,
         objects: Arc<Mutex<ObjectMap>>,
         data: Arc<Mutex<Vec<u8>>>,
+        open_arrays: Arc<Mutex<OpenArrays>>,
+        unique_fields: bool,
+        sequential_ids: bool,
+        nesting: Nesting,
     ) -> Result<Arc<Mutex<SpudObjectAsync>>, SpudError> {
+        if objects.lock().await.0.contains_key(&oid) {
+            return Err(SpudError::ValidationError(format!(
+                "object id {oid} is already used"
+            )));
+        }
+
         data.lock().await.extend_from_slice(&[
             SpudTypes::ObjectStart.as_u8(),
             SpudTypes::ObjectStart.as_u8(),
         ]);
-
-        let oid: ObjectId = Self::generate_oid(&mut data.lock().await)?;
+        data.lock().await.extend_from_slice(oid.as_bytes());
 
         let object: Arc<Mutex<SpudObjectAsync>> = Arc::new(Mutex::new(Self {
-            _oid: oid,
+            oid,
             data,
             field_names,
             seen_ids,
             objects: Arc::new(Mutex::new(ObjectMap(IndexMap::new()))),
+            open_arrays,
+            written_fields: unique_fields.then(|| Mutex::new(Vec::new())),
+            sequential_ids,
+            closed: false,
+            nesting,
         }));
 
         objects.lock().await.0.insert(oid, Arc::clone(&object));
@@ -60,10 +89,10 @@ impl SpudObjectAsync {
     /// # Examples
     ///
     /// ```rust
-    /// use spud_rs::{SpudBuilder, SpudObjectAsync};
+    /// use spud_rs::{SpudBuilderAsync, SpudObjectAsync};
     /// use tokio::sync::MutexGuard;
     ///
-    /// let builder = SpudBuilder::new();
+    /// let builder = SpudBuilderAsync::new();
     ///
     /// builder.object(async |obj| {
     ///     let locked_obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
@@ -97,6 +126,157 @@ impl SpudObjectAsync {
         Ok(self)
     }
 
+    /// Adds a field holding a type byte and payload written verbatim, for layouts the typed API does not cover.
+    ///
+    /// Nothing checks that `tag` is a known type or that `payload` has the length and layout that type expects.
+    /// A mismatch is not caught here but makes the file undecodable from this field on.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field to which the value will be added.
+    /// * `tag` - The type byte written before the payload.
+    /// * `payload` - The value bytes, written as they are.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the `SpudObjectAsync`, allowing for method chaining.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the field name cannot be added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub async fn add_raw_typed_unchecked(
+        &self,
+        field_name: &str,
+        tag: u8,
+        payload: &[u8],
+    ) -> Result<&Self, SpudError> {
+        self.add_field_name(field_name).await?;
+
+        let mut data: MutexGuard<'_, Vec<u8>> = self.data.lock().await;
+
+        data.push(tag);
+        data.extend_from_slice(payload);
+
+        Ok(self)
+    }
+
+    /// Adds a value to the object like `add_value`, after checking it against a schema.
+    ///
+    /// The field has to be declared in the schema, with a type the value's SPUD type satisfies.
+    /// Nothing is written when the check fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema` - The schema the field is checked against.
+    /// * `field_name` - The name of the field to add.
+    /// * `value` - The value to add, which must implement the `SpudTypesExt` trait.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudObjectAsync`, allowing for method chaining.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SpudError::ValidationError` if the field is not declared in the schema or has another type,
+    /// or the same errors as `add_value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub async fn add_value_checked<T: SpudTypesExt>(
+        &self,
+        schema: &SpudSchema,
+        field_name: &str,
+        value: T,
+    ) -> Result<&Self, SpudError> {
+        let mut value_bytes: Vec<u8> = Vec::new();
+
+        value.write_spud_bytes(&mut value_bytes);
+
+        if let Some(found) = value_bytes
+            .first()
+            .and_then(|&byte| SpudTypes::from_u8(byte))
+        {
+            schema.check_field(field_name, found)?;
+        }
+
+        self.add_field_name(field_name).await?;
+
+        self.data.lock().await.extend_from_slice(&value_bytes);
+
+        Ok(self)
+    }
+
+    /// Adds an enum value, stored as a single byte referring to the variant's name.
+    ///
+    /// Every variant name is interned in the header's field-name table, the same way field names are,
+    /// so each value only costs its tag and one byte, and decodes to the variant name as a string.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field to which the value will be added.
+    /// * `variant` - The variant to store, which must be one of `variants`.
+    /// * `variants` - Every variant of the enum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::{SpudBuilderAsync, SpudObjectAsync};
+    /// use tokio::sync::MutexGuard;
+    ///
+    /// let builder = SpudBuilderAsync::new();
+    ///
+    /// builder.object(async |obj| {
+    ///     let locked_obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+    ///
+    ///     locked_obj.add_enum("status", "active", &["active", "suspended", "closed"]).await?;
+    ///
+    ///     Ok(())
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudObjectAsync`, allowing for method chaining.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `variant` is not one of `variants`, if a name is longer than 255 bytes,
+    /// or if the field-name table has no ids left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub async fn add_enum(
+        &self,
+        field_name: &str,
+        variant: &str,
+        variants: &[&str],
+    ) -> Result<&Self, SpudError> {
+        if !variants.contains(&variant) {
+            return Err(SpudError::EncodingError(format!(
+                "unknown enum variant '{variant}'"
+            )));
+        }
+
+        self.add_field_name(field_name).await?;
+
+        for name in variants {
+            self.intern(name).await?;
+        }
+
+        let id: u8 = self.intern(variant).await?;
+
+        self.data.lock().await.push(SpudTypes::Enum.as_u8());
+        self.data.lock().await.push(id);
+
+        Ok(self)
+    }
+
     /// Creates a new `SpudObjectAsync` instance associated with this Object.
     ///
     /// # Arguments
@@ -115,24 +295,239 @@ impl SpudObjectAsync {
         F: FnOnce(Arc<Mutex<SpudObjectAsync>>) -> Fut,
         Fut: Future<Output = Result<(), SpudError>>,
     {
+        let nesting: Nesting = self.nesting.nested()?;
+
         self.add_field_name(field_name).await?;
 
-        let obj: Arc<Mutex<SpudObjectAsync>> = self.new_object().await?;
+        self.write_object(nesting, ObjectId::new()?, f).await
+    }
+
+    /// Adds a nested object field like `object`, but with a caller-chosen id instead of a freshly generated one.
+    ///
+    /// Useful when re-encoding records that already have stable ids, so consumers can keep correlating them.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SpudError::ValidationError` if this object already holds a nested object with the same id,
+    /// or an error if the object cannot be created or the closure fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub async fn object_with_id<F, Fut>(
+        &self,
+        field_name: &str,
+        oid: ObjectId,
+        f: F,
+    ) -> Result<(), SpudError>
+    where
+        F: FnOnce(Arc<Mutex<SpudObjectAsync>>) -> Fut,
+        Fut: Future<Output = Result<(), SpudError>>,
+    {
+        let nesting: Nesting = self.nesting.nested()?;
+
+        self.add_field_name(field_name).await?;
 
-        f(obj).await?;
+        self.write_object(nesting, oid, f).await
+    }
+
+    /// Adds a nested object field holding one field per entry of `map`, in the map's order.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field to which the object will be added.
+    /// * `map` - The entries to write, keyed by field name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object would exceed the builder's max nesting depth or if a field name,
+    /// including one of the keys, cannot be added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub async fn add_map<V: SpudTypesExt>(
+        &self,
+        field_name: &str,
+        map: &IndexMap<String, V>,
+    ) -> Result<(), SpudError> {
+        self.object(field_name, async move |obj: Arc<Mutex<SpudObjectAsync>>| {
+            let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+            for (key, value) in map {
+                obj.add_field_name(key).await?;
+
+                value.write_spud_bytes(&mut *obj.data.lock().await);
+            }
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Adds an array field whose elements are objects, each one built through the provided `SpudObjectArrayAsync`.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field to which the array will be added.
+    /// * `f` - A closure that pushes the objects through the provided `SpudObjectArrayAsync`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the objects would exceed the builder's max nesting depth,
+    /// if the field name cannot be added or if the closure fails.
+    pub async fn add_object_array<'a, F, Fut>(
+        &'a self,
+        field_name: &str,
+        f: F,
+    ) -> Result<(), SpudError>
+    where
+        F: FnOnce(Arc<Mutex<SpudObjectArrayAsync<'a>>>) -> Fut,
+        Fut: Future<Output = Result<(), SpudError>>,
+    {
+        let nesting: Nesting = self.nesting.nested()?;
+
+        self.add_field_name(field_name).await?;
+
+        self.data.lock().await.push(SpudTypes::ArrayStart.as_u8());
+
+        f(Arc::new(Mutex::new(SpudObjectArrayAsync::new(
+            self, nesting,
+        ))))
+        .await?;
+
+        self.data.lock().await.push(SpudTypes::ArrayEnd.as_u8());
+
+        Ok(())
+    }
+
+    /// Adds an array field whose elements are written straight into the data buffer as they are pushed.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field to which the array will be added.
+    /// * `f` - A closure that pushes the elements through the provided `SpudArrayAsync`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the field name cannot be added or if the closure fails.
+    pub async fn array<F, Fut>(&self, field_name: &str, f: F) -> Result<(), SpudError>
+    where
+        F: FnOnce(Arc<Mutex<SpudArrayAsync>>) -> Fut,
+        Fut: Future<Output = Result<(), SpudError>>,
+    {
+        self.add_field_name(field_name).await?;
+
+        self.data.lock().await.push(SpudTypes::ArrayStart.as_u8());
+
+        f(Arc::new(Mutex::new(SpudArrayAsync::new(Arc::clone(
+            &self.data,
+        )))))
+        .await?;
+
+        self.data.lock().await.push(SpudTypes::ArrayEnd.as_u8());
+
+        Ok(())
+    }
+
+    /// Appends values to an array field, opening the array on the first call for this field.
+    ///
+    /// Later calls for the same field insert their values just before the array's closing byte,
+    /// so the elements of every call end up in one array even when other fields were written in between.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the array field.
+    /// * `values` - The values to be appended, which must implement the `SpudTypesExt` trait.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the `SpudObjectAsync`, allowing for method chaining.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the field name cannot be added.
+    pub async fn append_to_array<T: SpudTypesExt>(
+        &self,
+        field_name: &str,
+        values: &[T],
+    ) -> Result<&Self, SpudError> {
+        self.ensure_open()?;
+
+        let mut encoded: Vec<u8> = Vec::new();
+
+        for value in values {
+            value.write_spud_bytes(&mut encoded);
+        }
+
+        let key: (ObjectId, String) = (self.oid, field_name.into());
+
+        let end: Option<usize> = self.open_arrays.lock().await.get(&key).copied();
+
+        if let Some(end) = end {
+            let inserted: usize = encoded.len();
+
+            self.data.lock().await.splice(end..end, encoded);
+
+            for position in self.open_arrays.lock().await.values_mut() {
+                if *position >= end {
+                    *position += inserted;
+                }
+            }
+        } else {
+            self.add_field_name(field_name).await?;
+
+            let mut data: MutexGuard<'_, Vec<u8>> = self.data.lock().await;
+
+            data.push(SpudTypes::ArrayStart.as_u8());
+            data.extend_from_slice(&encoded);
+
+            self.open_arrays.lock().await.insert(key, data.len());
+
+            data.push(SpudTypes::ArrayEnd.as_u8());
+        }
+
+        Ok(self)
+    }
+
+    /// Writes a nested object at `nesting` into the data buffer, letting `f` fill in its fields.
+    pub(crate) async fn write_object<F, Fut>(
+        &self,
+        nesting: Nesting,
+        oid: ObjectId,
+        f: F,
+    ) -> Result<(), SpudError>
+    where
+        F: FnOnce(Arc<Mutex<SpudObjectAsync>>) -> Fut,
+        Fut: Future<Output = Result<(), SpudError>>,
+    {
+        let obj: Arc<Mutex<SpudObjectAsync>> = self.new_object(nesting, oid).await?;
+
+        f(Arc::clone(&obj)).await?;
 
         self.data.lock().await.push(SpudTypes::ObjectEnd.as_u8());
         self.data.lock().await.push(SpudTypes::ObjectEnd.as_u8());
 
+        obj.lock().await.close();
+
         Ok(())
     }
 
-    async fn new_object(&self) -> Result<Arc<Mutex<SpudObjectAsync>>, SpudError> {
+    async fn new_object(
+        &self,
+        nesting: Nesting,
+        oid: ObjectId,
+    ) -> Result<Arc<Mutex<SpudObjectAsync>>, SpudError> {
         SpudObjectAsync::new(
+            oid,
             Arc::clone(&self.field_names),
             Arc::clone(&self.seen_ids),
             Arc::clone(&self.objects),
             Arc::clone(&self.data),
+            Arc::clone(&self.open_arrays),
+            self.written_fields.is_some(),
+            self.sequential_ids,
+            nesting,
         )
         .await
     }
@@ -152,17 +547,49 @@ impl SpudObjectAsync {
         })
     }
 
+    /// Marks the object as finished, any later write through a retained handle is rejected.
+    pub(crate) fn close(&mut self) {
+        self.closed = true;
+    }
+
+    /// Closes the object and drops its nested objects, each of which holds a handle to this object's map.
+    pub(crate) fn release(&mut self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            self.close();
+
+            let objects: Vec<(ObjectId, Arc<Mutex<SpudObjectAsync>>)> =
+                self.objects.lock().await.0.drain(..).collect();
+
+            for (_, object) in objects {
+                object.lock().await.release().await;
+            }
+        })
+    }
+
+    fn ensure_open(&self) -> Result<(), SpudError> {
+        if self.closed {
+            return Err(SpudError::EncodingError("object already closed".to_owned()));
+        }
+
+        Ok(())
+    }
+
     async fn add_field_name(&self, field_name: &str) -> Result<&Self, SpudError> {
-        let key: (String, u8) = (field_name.into(), u8::try_from(field_name.len())?);
+        self.ensure_open()?;
 
-        let id: u8 = if let Some(value) = self.field_names.lock().await.get(&key) {
-            *value
-        } else {
-            let id: u8 = generate_u8_id_async(&mut self.seen_ids.lock().await)?;
+        let id: u8 = self.intern(field_name).await?;
 
-            self.field_names.lock().await.insert(key, id);
-            id
-        };
+        if let Some(written_fields) = &self.written_fields {
+            let mut written_fields: MutexGuard<'_, Vec<u8>> = written_fields.lock().await;
+
+            if written_fields.contains(&id) {
+                return Err(SpudError::ValidationError(format!(
+                    "field '{field_name}' is already set on this object"
+                )));
+            }
+
+            written_fields.push(id);
+        }
 
         self.data.lock().await.push(SpudTypes::FieldNameId.as_u8());
         self.data.lock().await.push(id);
@@ -170,11 +597,24 @@ impl SpudObjectAsync {
         Ok(self)
     }
 
-    fn generate_oid(data: &mut MutexGuard<'_, Vec<u8>>) -> Result<ObjectId, SpudError> {
-        let oid: ObjectId = ObjectId::new()?;
+    /// Returns the id `name` has in the field-name table, adding it first if needed.
+    ///
+    /// Fresh ids are random unless the builder is deterministic, in which case the lowest free id is taken.
+    async fn intern(&self, name: &str) -> Result<u8, SpudError> {
+        let key: (String, u8) = (name.into(), field_name_len(name)?);
+
+        if let Some(value) = self.field_names.lock().await.get(&key) {
+            return Ok(*value);
+        }
+
+        let id: u8 = if self.sequential_ids {
+            next_u8_id(&mut self.seen_ids.lock().await)?
+        } else {
+            generate_u8_id_async(&mut self.seen_ids.lock().await)?
+        };
 
-        data.extend_from_slice(oid.as_bytes());
+        self.field_names.lock().await.insert(key, id);
 
-        Ok(oid)
+        Ok(id)
     }
 }
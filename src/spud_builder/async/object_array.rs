@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::{SpudError, spud_builder::Nesting, types::ObjectId};
+
+use super::SpudObjectAsync;
+
+/// Represents a SPUD array of objects that is being built in place.
+/// Every pushed object is written straight into the builder's data buffer, right after the previous one.
+#[derive(Debug)]
+pub struct SpudObjectArrayAsync<'a> {
+    parent: &'a SpudObjectAsync,
+    nesting: Nesting,
+}
+
+impl<'a> SpudObjectArrayAsync<'a> {
+    pub(crate) fn new(parent: &'a SpudObjectAsync, nesting: Nesting) -> Self {
+        Self { parent, nesting }
+    }
+
+    /// Appends an object to the array.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure that adds the object's fields through the provided `SpudObjectAsync`.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the `SpudObjectArrayAsync`, allowing for method chaining.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object cannot be created or if the closure fails.
+    pub async fn push_object<F, Fut>(&self, f: F) -> Result<&Self, SpudError>
+    where
+        F: FnOnce(Arc<Mutex<SpudObjectAsync>>) -> Fut,
+        Fut: Future<Output = Result<(), SpudError>>,
+    {
+        self.parent
+            .write_object(self.nesting, ObjectId::new()?, f)
+            .await?;
+
+        Ok(self)
+    }
+}
@@ -3,10 +3,16 @@ mod r#async;
 #[cfg(feature = "async")]
 pub use r#async::*;
 
+#[cfg(feature = "sync")]
+mod spud_object_macro;
 #[cfg(feature = "sync")]
 mod sync;
 #[cfg(feature = "sync")]
 pub use sync::*;
 
-#[cfg(any(feature = "sync", feature = "async"))]
 mod spud_type_ext;
+pub use spud_type_ext::SpudTypesExt;
+#[cfg(any(feature = "sync", feature = "async"))]
+mod nesting;
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(crate) use nesting::Nesting;
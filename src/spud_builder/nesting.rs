@@ -0,0 +1,32 @@
+use crate::SpudError;
+
+/// How deeply an object is nested, top-level objects being at depth 1, and the limit set on its builder.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Nesting {
+    depth: usize,
+    max_depth: Option<usize>,
+}
+
+impl Nesting {
+    pub(crate) fn top_level(max_depth: Option<usize>) -> Self {
+        Self {
+            depth: 1,
+            max_depth,
+        }
+    }
+
+    pub(crate) fn nested(self) -> Result<Self, SpudError> {
+        let depth: usize = self.depth + 1;
+
+        if self.max_depth.is_some_and(|max_depth| depth > max_depth) {
+            return Err(SpudError::EncodingError(
+                "max nesting depth exceeded".to_owned(),
+            ));
+        }
+
+        Ok(Self {
+            depth,
+            max_depth: self.max_depth,
+        })
+    }
+}
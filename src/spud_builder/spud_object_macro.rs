@@ -0,0 +1,145 @@
+/// Builds a single SPUD object declaratively and encodes it.
+///
+/// Every `"field": value` pair is turned into a builder call:
+/// - `{ ... }` becomes a nested object,
+/// - `[a, b, ...]` becomes an array, all elements must share a type,
+/// - any other expression is passed to `add_value`.
+///
+/// Empty arrays can't infer their element type, use an expression like `Vec::<u8>::new()` instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use spud_rs::spud_object;
+///
+/// let encoded_bytes: Vec<u8> = spud_object! {
+///     "name": "Alice",
+///     "age": 30u8,
+///     "tags": ["a", "b"],
+///     "address": {
+///         "city": "Rome",
+///     },
+/// }
+/// .unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Expands to a `Result<Vec<u8>, SpudError>`, which is an error if any of the builder calls fails.
+#[macro_export]
+macro_rules! spud_object {
+    ($($body:tt)*) => {{
+        let builder: $crate::SpudBuilderSync = $crate::SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &$crate::SpudObjectSync| {
+                $crate::__spud_object_fields!(obj; $($body)*);
+
+                Ok(())
+            })
+            .and_then(|()| builder.encode())
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __spud_object_fields {
+    ($obj:ident;) => {
+        let _ = $obj;
+    };
+    ($obj:ident; $field:literal : { $($inner:tt)* } $(, $($rest:tt)*)?) => {
+        $obj.object($field, |inner: &$crate::SpudObjectSync| {
+            $crate::__spud_object_fields!(inner; $($inner)*);
+
+            Ok(())
+        })?;
+
+        $crate::__spud_object_fields!($obj; $($($rest)*)?);
+    };
+    ($obj:ident; $field:literal : [ $($item:expr),* $(,)? ] $(, $($rest:tt)*)?) => {
+        $obj.add_value($field, vec![$($item),*])?;
+
+        $crate::__spud_object_fields!($obj; $($($rest)*)?);
+    };
+    ($obj:ident; $field:literal : $value:expr $(, $($rest:tt)*)?) => {
+        $obj.add_value($field, $value)?;
+
+        $crate::__spud_object_fields!($obj; $($($rest)*)?);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::{SpudBuilderSync, SpudDecoder, types::SpudString};
+
+    fn decode_without_oids(encoded_bytes: &[u8]) -> Value {
+        fn strip(value: &mut Value) {
+            match value {
+                Value::Object(object) => {
+                    object.remove("oid");
+                    object.values_mut().for_each(strip);
+                }
+                Value::Array(values) => values.iter_mut().for_each(strip),
+                _ => {}
+            }
+        }
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(encoded_bytes).unwrap();
+
+        let mut value: Value = serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        strip(&mut value);
+
+        value
+    }
+
+    #[test]
+    fn test_spud_object_macro() {
+        let from_macro: Vec<u8> = spud_object! {
+            "name": "Alice",
+            "age": 30u8,
+            "tags": ["a", "b"],
+            "scores": [1u16, 2u16, 3u16],
+            "address": {
+                "city": "Rome",
+                "geo": { "lat": 41.9f64, "lon": 12.5f64 },
+            },
+            "active": true
+        }
+        .unwrap();
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("name", SpudString::from("Alice"))?;
+                obj.add_value("age", 30u8)?;
+                obj.add_value("tags", vec![SpudString::from("a"), SpudString::from("b")])?;
+                obj.add_value("scores", vec![1u16, 2u16, 3u16])?;
+                obj.object("address", |address| {
+                    address.add_value("city", SpudString::from("Rome"))?;
+                    address.object("geo", |geo| {
+                        geo.add_value("lat", 41.9f64)?;
+                        geo.add_value("lon", 12.5f64)?;
+
+                        Ok(())
+                    })?;
+
+                    Ok(())
+                })?;
+                obj.add_value("active", true)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let by_hand: Vec<u8> = builder.encode().unwrap();
+
+        assert_eq!(
+            decode_without_oids(&from_macro),
+            decode_without_oids(&by_hand)
+        );
+    }
+}
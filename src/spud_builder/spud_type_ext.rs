@@ -1,16 +1,30 @@
+use alloc::{string::String, vec::Vec};
+use core::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    ops::{Range, RangeInclusive},
+    time::Duration,
+};
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+
+use chrono::TimeDelta;
 use rust_decimal::Decimal;
 
+#[cfg(feature = "std")]
+use crate::types::{BinaryBlob as BinaryBlobStruct, SpudString, Uuid};
 use crate::{
     functions::add_value_length,
     spud_types::{SpudNumberTypes, SpudTypes},
-    types::{BinaryBlob as BinaryBlobStruct, Date, DateTime, SpudString, Time},
+    types::{self, Date, DateTime, Time},
 };
 
 trait SpudPrimitiveWriter {
     fn write_primitive(self, data: &mut Vec<u8>);
 }
 
+/// A value that can be written as a SPUD value, its type byte followed by its payload.
 pub trait SpudTypesExt {
+    /// Appends the encoded value to `data`.
     fn write_spud_bytes(&self, data: &mut Vec<u8>);
 }
 
@@ -71,20 +85,34 @@ impl_spud_type_ext! {
 impl_spud_type_ext! {
     Decimal, Decimal, write_decimal,
     bool, Bool, write_bool,
+    char, Char, write_char,
     (), Null, write_null,
     Date, Date, write_date,
     Time, Time, write_time,
     DateTime, DateTime, write_datetime,
+    TimeDelta, Duration, write_duration,
+    types::Duration, Duration, write_elapsed,
+    IpAddr, IpAddr, write_ip_addr,
+    Ipv4Addr, IpAddr, write_ipv4_addr,
+    Ipv6Addr, IpAddr, write_ipv6_addr,
+}
+
+#[cfg(feature = "std")]
+impl_spud_type_ext! {
+    Uuid, Uuid, write_uuid,
 }
 
 fn write_bool(value: bool, data: &mut Vec<u8>) {
     data.push(u8::from(value));
 }
 
-fn write_null(_value: (), data: &mut Vec<u8>) {
-    data.push(SpudTypes::Null.as_u8());
+fn write_char(value: char, data: &mut Vec<u8>) {
+    data.extend_from_slice(&u32::from(value).to_le_bytes());
 }
 
+/// `Null` has no payload, the type byte written by `impl_spud_type_ext!` is the whole value.
+fn write_null(_value: (), _data: &mut Vec<u8>) {}
+
 fn write_primitive_value<T: SpudPrimitiveWriter>(value: T, data: &mut Vec<u8>) {
     value.write_primitive(data);
 }
@@ -107,6 +135,59 @@ fn write_datetime(value: DateTime, data: &mut Vec<u8>) {
     data.extend_from_slice(&value.as_le_bytes());
 }
 
+/// Writes whole seconds as a signed `i64` followed by the nanoseconds as a `u32` below one billion, so
+/// negative durations round the seconds down: -1.5s is stored as -2s and 500,000,000ns.
+fn write_duration(value: TimeDelta, data: &mut Vec<u8>) {
+    let mut secs: i64 = value.num_seconds();
+    let mut nanos: i32 = value.subsec_nanos();
+
+    if nanos < 0 {
+        secs -= 1;
+        nanos += 1_000_000_000;
+    }
+
+    data.extend_from_slice(&secs.to_le_bytes());
+    data.extend_from_slice(&nanos.unsigned_abs().to_le_bytes());
+}
+
+fn write_ip_addr(value: IpAddr, data: &mut Vec<u8>) {
+    match value {
+        IpAddr::V4(address) => write_ipv4_addr(address, data),
+        IpAddr::V6(address) => write_ipv6_addr(address, data),
+    }
+}
+
+/// Addresses start with their version, 4 or 6, followed by the address bytes in network order.
+fn write_ipv4_addr(value: Ipv4Addr, data: &mut Vec<u8>) {
+    data.push(4);
+    data.extend_from_slice(&value.octets());
+}
+
+/// IPv4-mapped addresses such as `::ffff:192.0.2.1` keep their version 6 discriminant.
+fn write_ipv6_addr(value: Ipv6Addr, data: &mut Vec<u8>) {
+    data.push(6);
+    data.extend_from_slice(&value.octets());
+}
+
+fn write_elapsed(value: types::Duration, data: &mut Vec<u8>) {
+    data.extend_from_slice(&value.as_le_bytes());
+}
+
+/// Elapsed durations share the `Duration` tag with `chrono::TimeDelta`, those longer than
+/// `TimeDelta::MAX` (about 292 million years) are clamped to it.
+impl SpudTypesExt for Duration {
+    fn write_spud_bytes(&self, data: &mut Vec<u8>) {
+        TimeDelta::from_std(*self)
+            .unwrap_or(TimeDelta::MAX)
+            .write_spud_bytes(data);
+    }
+}
+
+#[cfg(feature = "std")]
+fn write_uuid(value: Uuid, data: &mut Vec<u8>) {
+    data.extend_from_slice(value.as_bytes());
+}
+
 fn write_slice<T: SpudTypesExt>(slice: &[T], data: &mut Vec<u8>) {
     data.push(SpudTypes::ArrayStart.as_u8());
 
@@ -135,6 +216,46 @@ impl<T: SpudTypesExt, const L: usize> SpudTypesExt for &[T; L] {
     }
 }
 
+fn write_bounds<T: SpudTypesExt>(start: &T, end: &T, data: &mut Vec<u8>) {
+    data.push(SpudTypes::ArrayStart.as_u8());
+
+    start.write_spud_bytes(data);
+    end.write_spud_bytes(data);
+
+    data.push(SpudTypes::ArrayEnd.as_u8());
+}
+
+impl<T: SpudTypesExt> SpudTypesExt for Range<T> {
+    fn write_spud_bytes(&self, data: &mut Vec<u8>) {
+        write_bounds(&self.start, &self.end, data);
+    }
+}
+
+impl<T: SpudTypesExt> SpudTypesExt for RangeInclusive<T> {
+    fn write_spud_bytes(&self, data: &mut Vec<u8>) {
+        write_bounds(self.start(), self.end(), data);
+    }
+}
+
+/// `Some` is written as its inner value and `None` as `Null`, so optional fields need no branching at the call site.
+impl<T: SpudTypesExt> SpudTypesExt for Option<T> {
+    fn write_spud_bytes(&self, data: &mut Vec<u8>) {
+        match self {
+            Some(value) => value.write_spud_bytes(data),
+            None => ().write_spud_bytes(data),
+        }
+    }
+}
+
+fn write_str(value: &str, data: &mut Vec<u8>) {
+    data.push(SpudTypes::String.as_u8());
+
+    add_value_length(data, value.len());
+
+    data.extend_from_slice(value.as_bytes());
+}
+
+#[cfg(feature = "std")]
 impl SpudTypesExt for SpudString {
     fn write_spud_bytes(&self, data: &mut Vec<u8>) {
         data.push(SpudTypes::String.as_u8());
@@ -145,6 +266,39 @@ impl SpudTypesExt for SpudString {
     }
 }
 
+impl SpudTypesExt for &str {
+    fn write_spud_bytes(&self, data: &mut Vec<u8>) {
+        write_str(self, data);
+    }
+}
+
+impl SpudTypesExt for String {
+    fn write_spud_bytes(&self, data: &mut Vec<u8>) {
+        write_str(self, data);
+    }
+}
+
+/// Paths are written as strings, replacing any non-UTF-8 sequence with `U+FFFD`.
+///
+/// Use `SpudString::try_from` instead to reject such paths.
+#[cfg(feature = "std")]
+impl SpudTypesExt for &Path {
+    fn write_spud_bytes(&self, data: &mut Vec<u8>) {
+        write_str(&self.to_string_lossy(), data);
+    }
+}
+
+/// Paths are written as strings, replacing any non-UTF-8 sequence with `U+FFFD`.
+///
+/// Use `SpudString::try_from` instead to reject such paths.
+#[cfg(feature = "std")]
+impl SpudTypesExt for PathBuf {
+    fn write_spud_bytes(&self, data: &mut Vec<u8>) {
+        write_str(&self.to_string_lossy(), data);
+    }
+}
+
+#[cfg(feature = "std")]
 impl SpudTypesExt for BinaryBlobStruct<'_> {
     fn write_spud_bytes(&self, data: &mut Vec<u8>) {
         data.push(SpudTypes::BinaryBlob.as_u8());
@@ -0,0 +1,79 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use std::sync::{Arc, Mutex};
+
+use crate::{SpudError, spud_builder::spud_type_ext::SpudTypesExt};
+
+/// Represents a SPUD array that is being built in place.
+/// Every pushed element is written straight into the builder's data buffer, so elements are never retained.
+#[derive(Debug)]
+pub struct SpudArraySync {
+    data: Arc<Mutex<Vec<u8>>>,
+}
+
+impl SpudArraySync {
+    pub(crate) fn new(data: Arc<Mutex<Vec<u8>>>) -> Self {
+        Self { data }
+    }
+
+    /// Appends a value to the array.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to be appended, which must implement the `SpudTypesExt` trait.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::{SpudBuilderSync, SpudArraySync};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.array("readings", |arr: &SpudArraySync| {
+    ///         arr.push(1u8)?.push(2u8)?;
+    ///
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A reference to the `SpudArraySync`, allowing for method chaining.
+    ///
+    /// # Errors
+    ///
+    /// This method currently never fails, the `Result` mirrors `SpudObjectSync::add_value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn push<T: SpudTypesExt>(&self, value: T) -> Result<&Self, SpudError> {
+        value.write_spud_bytes(&mut self.data.lock().unwrap());
+
+        Ok(self)
+    }
+
+    /// Appends every value yielded by an iterator to the array, without collecting it first.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - An iterator of values, each of which must implement the `SpudTypesExt` trait.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the values cannot be pushed.
+    pub fn extend<T: SpudTypesExt, I: IntoIterator<Item = T>>(
+        &self,
+        values: I,
+    ) -> Result<&Self, SpudError> {
+        for value in values {
+            self.push(value)?;
+        }
+
+        Ok(self)
+    }
+}
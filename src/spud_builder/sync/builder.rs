@@ -1,22 +1,47 @@
 use indexmap::IndexMap;
-use std::{fmt, path::Path, sync::Arc};
+use std::{fmt, ops::Range, path::Path, sync::Arc};
 
-use std::sync::Mutex;
+use std::sync::{Mutex, MutexGuard};
 
 use crate::{
-    SpudError,
-    functions::{check_path, initialise_header_sync},
+    OBJECT_FORMAT_VERSION, SPUD_VERSION, SpudError,
+    functions::{
+        check_path, crc32, field_name_len, initialise_header_sync, write_field_footer,
+        write_field_table,
+    },
+    spud_builder::Nesting,
     spud_types::SpudTypes,
-    types::ObjectId,
+    types::{Capabilities, DateTime, ObjectId},
 };
 
-use std::fs;
+use std::{
+    fs,
+    io::{Seek, SeekFrom, Write},
+};
+
+use super::{SpudObjectSync, object::intern_field_name};
 
-use super::SpudObjectSync;
+#[cfg(feature = "test-utils")]
+use crate::SpudDecoder;
 
 #[derive(Default, Clone)]
 pub(crate) struct ObjectMap(pub(crate) IndexMap<ObjectId, Arc<Mutex<SpudObjectSync>>>);
 
+impl ObjectMap {
+    /// Empties the map, releasing every object in it so none is kept alive by a reference cycle.
+    fn clear(&mut self) {
+        for (_, object) in self.0.drain(..) {
+            object.lock().unwrap().release();
+        }
+    }
+}
+
+/// Position of the closing `ArrayEnd` byte of every array opened through `append_to_array`, keyed by owning object and field name.
+pub(crate) type OpenArrays = IndexMap<(ObjectId, String), usize>;
+
+/// Byte range of every finished top-level object in the data buffer, in creation order.
+type ObjectSpans = Vec<(ObjectId, Range<usize>)>;
+
 /// Represents a builder for creating SPUD objects.
 ///
 /// This builder allows you to create and manage SPUD objects, encode them into a byte vector, and write them to a file.
@@ -35,6 +60,13 @@ pub struct SpudBuilderSync {
     pub(crate) data: Arc<Mutex<Vec<u8>>>,
     pub(crate) objects: Arc<Mutex<ObjectMap>>,
     pub(crate) seen_ids: Arc<Mutex<Vec<bool>>>,
+    pub(crate) open_arrays: Arc<Mutex<OpenArrays>>,
+    object_spans: Arc<Mutex<ObjectSpans>>,
+    auto_timestamp: Option<String>,
+    max_depth: Option<usize>,
+    unique_fields: bool,
+    deterministic: bool,
+    capabilities: Capabilities,
 }
 
 impl SpudBuilderSync {
@@ -63,128 +95,1023 @@ impl SpudBuilderSync {
             data: Arc::new(Mutex::new(Vec::new())),
             objects: Arc::new(Mutex::new(ObjectMap(IndexMap::new()))),
             seen_ids: Arc::new(Mutex::new(seen_ids)),
+            open_arrays: Arc::new(Mutex::new(IndexMap::new())),
+            object_spans: Arc::new(Mutex::new(Vec::new())),
+            auto_timestamp: None,
+            max_depth: None,
+            unique_fields: false,
+            deterministic: false,
+            capabilities: Capabilities::empty(),
         }
     }
 
-    /// Creates a new `SpudObjectSync` instance associated with this builder.
+    #[must_use]
+    /// Creates a new `SpudBuilderSync` whose output depends only on what is added to it, not on chance.
+    ///
+    /// Field names are handed the lowest free id in the order they are first used, instead of a random one,
+    /// and `encode` writes the field-name table sorted by name. Adding the same values in the same order
+    /// therefore yields the same field table and the same object bodies on every run.
+    ///
+    /// Object ids are still generated from the clock and a per-process random value, so two files built
+    /// at different times differ in those 10 bytes per object.
+    ///
+    /// # Tradeoffs
+    ///
+    /// Ids are assigned as values are written, so objects can still be streamed out as they are finished,
+    /// but the sorted table can only be written once every name is known: either after the last object,
+    /// or after the objects with `with_footer_field_table`. Ids also no longer hide which names came first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new_deterministic();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("name", "Alice")?;
+    ///     obj.add_value("age", 30u8)?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// assert_eq!(builder.ensure_field("name").unwrap(), 2);
+    /// assert_eq!(builder.ensure_field("age").unwrap(), 3);
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `SpudBuilderSync` with deterministic field ids.
+    pub fn new_deterministic() -> Self {
+        Self {
+            deterministic: true,
+            ..Self::new()
+        }
+    }
+
+    #[must_use]
+    /// Creates a new `SpudBuilderSync` whose data buffer can hold `bytes` bytes of encoded objects before it reallocates.
+    ///
+    /// Every value is appended to one shared buffer, so when the size of the output is roughly known up front,
+    /// reserving it here avoids the repeated reallocation and copying of a buffer that grows from empty.
     ///
     /// # Arguments
     ///
-    /// * `f` - A closure that takes a reference to the `SpudObjectSync` and returns a `Result<(), SpudError>`.
+    /// * `bytes` - The number of bytes to reserve, it does not limit how much can be written.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use spud_rs::SpudBuilderSync;
     ///
-    /// let builder = SpudBuilderSync::new();
+    /// let builder = SpudBuilderSync::with_capacity(64 * 1024);
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `SpudBuilderSync` with a pre-allocated data buffer.
+    pub fn with_capacity(bytes: usize) -> Self {
+        Self {
+            data: Arc::new(Mutex::new(Vec::with_capacity(bytes))),
+            ..Self::new()
+        }
+    }
+
+    /// Stamps every top-level object with the time it was created, stored as a `DateTime` in UTC.
+    ///
+    /// The timestamp is written as the object's first field, nested objects are not stamped.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field holding the timestamp, e.g. `created_at`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let mut builder = SpudBuilderSync::new();
+    ///
+    /// builder.with_auto_timestamp("created_at");
     ///
     /// builder.object(|obj| {
+    ///     obj.add_value("name", "Alice")?;
+    ///
     ///     Ok(())
+    /// }).unwrap();
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudBuilderSync`, allowing for method chaining.
+    pub fn with_auto_timestamp(&mut self, field_name: &str) -> &mut Self {
+        self.auto_timestamp = Some(field_name.to_owned());
+
+        self
+    }
+
+    /// Limits how deeply objects can be nested, top-level objects being at depth 1.
+    ///
+    /// A nested `object()` call that would go past the limit returns
+    /// `SpudError::EncodingError("max nesting depth exceeded")` without writing anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_depth` - The deepest level an object can be created at.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let mut builder = SpudBuilderSync::new();
+    ///
+    /// builder.with_max_depth(2);
+    ///
+    /// let result = builder.object(|obj| {
+    ///     obj.object("child", |child| {
+    ///         child.object("grandchild", |_| Ok(()))
+    ///     })
     /// });
+    ///
+    /// assert!(result.is_err());
     /// ```
     ///
     /// # Returns
     ///
-    /// A new instance of `SpudObjectSync` that is linked to the builder's field names, seen IDs, and objects.
+    /// A mutable reference to the `SpudBuilderSync`, allowing for method chaining.
+    pub fn with_max_depth(&mut self, max_depth: usize) -> &mut Self {
+        self.max_depth = Some(max_depth);
+
+        self
+    }
+
+    /// Rejects writing the same field twice on one object, which would otherwise keep both entries
+    /// and let the last one win on decode.
     ///
-    /// # Errors
+    /// A second `add_value`, `object`, `array` or other field write with a name the object already holds
+    /// returns `SpudError::ValidationError`. Extending an array with `append_to_array` is not a duplicate.
     ///
-    /// Returns an error if the object cannot be created, typically due to internal issues with the builder's state.
+    /// # Examples
     ///
-    /// # Panics
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
     ///
-    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    /// let mut builder = SpudBuilderSync::new();
     ///
-    /// # Note
+    /// builder.with_unique_fields();
     ///
-    /// The `SpudObjectSync` created by this method will share the same field names, seen IDs, and objects as the builder.
-    pub fn object<F>(&self, f: F) -> Result<(), SpudError>
-    where
-        F: FnOnce(&SpudObjectSync) -> Result<(), SpudError>,
-    {
-        let obj: Arc<Mutex<SpudObjectSync>> = self.new_object()?;
+    /// let result = builder.object(|obj| {
+    ///     obj.add_value("x", 1u8)?;
+    ///     obj.add_value("x", 2u8)?;
+    ///
+    ///     Ok(())
+    /// });
+    ///
+    /// assert!(result.is_err());
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudBuilderSync`, allowing for method chaining.
+    pub fn with_unique_fields(&mut self) -> &mut Self {
+        self.unique_fields = true;
 
-        f(&obj.lock().unwrap())?;
+        self
+    }
 
-        self.data.lock().unwrap().push(SpudTypes::ObjectEnd.as_u8());
-        self.data.lock().unwrap().push(SpudTypes::ObjectEnd.as_u8());
+    /// Follows every top-level object with a CRC-32 of its bytes, so the decoder can tell which objects were corrupted.
+    ///
+    /// The checksum is written as a little-endian `u32` right after the object's `ObjectEnd ObjectEnd` bytes,
+    /// and the file declares `Capabilities::CHECKSUM` in its trailer. Nested objects are covered by their top-level object.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder, types::Capabilities};
+    ///
+    /// let mut builder = SpudBuilderSync::new();
+    ///
+    /// builder.with_object_checksums();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("name", "Alice")?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+    ///
+    /// let decoder = SpudDecoder::new(&encoded_bytes).unwrap();
+    ///
+    /// assert!(decoder.capabilities().contains(Capabilities::CHECKSUM));
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudBuilderSync`, allowing for method chaining.
+    pub fn with_object_checksums(&mut self) -> &mut Self {
+        self.capabilities.insert(Capabilities::CHECKSUM);
 
-        Ok(())
+        self
     }
 
-    fn new_object(&self) -> Result<Arc<Mutex<SpudObjectSync>>, SpudError> {
-        SpudObjectSync::new(
-            Arc::clone(&self.field_names),
-            Arc::clone(&self.seen_ids),
-            Arc::clone(&self.objects),
-            Arc::clone(&self.data),
-        )
+    /// Stamps every top-level object with the per-object format version, `OBJECT_FORMAT_VERSION`.
+    ///
+    /// The version is written as one byte right after the object's id, and the file declares
+    /// `Capabilities::OBJECT_VERSION` in its trailer. Nested objects share the version of their top-level object.
+    ///
+    /// Archives that keep appending objects across releases can then mix object layouts: a decoder reads each
+    /// object with the rules of its own version, and rejects versions newer than it knows instead of misreading them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder, types::Capabilities};
+    ///
+    /// let mut builder = SpudBuilderSync::new();
+    ///
+    /// builder.with_object_versions();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("name", "Alice")?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+    ///
+    /// let decoder = SpudDecoder::new(&encoded_bytes).unwrap();
+    ///
+    /// assert!(decoder.capabilities().contains(Capabilities::OBJECT_VERSION));
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudBuilderSync`, allowing for method chaining.
+    pub fn with_object_versions(&mut self) -> &mut Self {
+        self.capabilities.insert(Capabilities::OBJECT_VERSION);
+
+        self
     }
 
-    /// Encodes all objects associated with this builder into a byte vector.
+    /// Writes the field-name table after the objects instead of before them.
+    ///
+    /// The header keeps an empty table, the real one follows the last object together with its position in the
+    /// file as a little-endian `u64`, and the file declares `Capabilities::FOOTER_FIELD_TABLE` in its trailer.
+    /// Decoders that predate the flag reject such files instead of reading them without field names.
+    ///
+    /// This is the layout a single-pass writer can produce, as nothing written before the objects depends
+    /// on the field names they end up using.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use spud_rs::SpudBuilderSync;
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder, types::Capabilities};
     ///
-    /// let builder = SpudBuilderSync::new();
+    /// let mut builder = SpudBuilderSync::new();
+    ///
+    /// builder.with_footer_field_table();
     ///
     /// builder.object(|obj| {
+    ///     obj.add_value("name", "Alice")?;
+    ///
     ///     Ok(())
-    /// });
+    /// }).unwrap();
     ///
-    /// let encoded_data = builder.encode().unwrap();
+    /// let mut bytes: Vec<u8> = Vec::new();
+    ///
+    /// builder.encode_to_writer(&mut bytes).unwrap();
+    ///
+    /// let decoder = SpudDecoder::new(&bytes).unwrap();
+    ///
+    /// assert!(decoder.capabilities().contains(Capabilities::FOOTER_FIELD_TABLE));
     /// ```
     ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudBuilderSync`, allowing for method chaining.
+    pub fn with_footer_field_table(&mut self) -> &mut Self {
+        self.capabilities.insert(Capabilities::FOOTER_FIELD_TABLE);
+
+        self
+    }
+
+    /// Replaces the field-name table with a fixed name to id mapping, e.g. to match the header layout of another reader.
+    ///
+    /// Values added afterwards reuse these ids, names missing from the table are given one of the remaining ids.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - Every field name paired with the id it is written with, in header order.
+    ///
     /// # Errors
     ///
-    /// Returns an error if any of the objects cannot be encoded, typically due to issues with the data format or internal state.
+    /// Returns an error if a value has already been written, if an id is reserved (`0` and `1`) or used twice,
+    /// or if a name appears twice or is longer than 255 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.set_field_table(&[("name", 2), ("age", 3)]).unwrap();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("name", "Alice")?;
+    ///     obj.add_value("age", 30u8)?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
     ///
     /// # Panics
     ///
     /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
-    pub fn encode(&self) -> Result<Vec<u8>, SpudError> {
-        for object in self.objects.lock().unwrap().0.values() {
-            object.lock().unwrap().encode()?;
+    pub fn set_field_table(&self, names: &[(&str, u8)]) -> Result<(), SpudError> {
+        if !self.data.lock().unwrap().is_empty() {
+            return Err(SpudError::EncodingError(
+                "field table must be set before any value is written".to_owned(),
+            ));
         }
 
-        let header: Vec<u8> = initialise_header_sync(
-            &self.field_names.lock().unwrap(),
-            &self.data.lock().unwrap(),
-        );
+        let mut field_names: IndexMap<(String, u8), u8> = IndexMap::new();
+        let mut seen_ids: Vec<bool> = vec![false; 256];
 
-        self.data.lock().unwrap().clear();
-        self.data.lock().unwrap().extend_from_slice(&header);
+        seen_ids[0] = true;
+        seen_ids[1] = true;
 
-        Ok(header)
+        for &(name, id) in names {
+            if id <= 1 {
+                return Err(SpudError::EncodingError(format!(
+                    "field id {id} is reserved"
+                )));
+            }
+
+            if seen_ids[usize::from(id)] {
+                return Err(SpudError::EncodingError(format!(
+                    "field id {id} is used more than once"
+                )));
+            }
+
+            let key: (String, u8) = (name.to_owned(), field_name_len(name)?);
+
+            if field_names.contains_key(&key) {
+                return Err(SpudError::EncodingError(format!(
+                    "field name '{name}' is used more than once"
+                )));
+            }
+
+            seen_ids[usize::from(id)] = true;
+            field_names.insert(key, id);
+        }
+
+        *self.field_names.lock().unwrap() = field_names;
+        *self.seen_ids.lock().unwrap() = seen_ids;
+
+        Ok(())
     }
 
-    /// Builds the SPUD file at the specified path with the given file name.
+    /// Returns the id of a field name, adding the name to the field-name table with a fresh id if it is not there yet.
     ///
-    ///  # Arguments
+    /// Ids already in the table are never reassigned, which makes this the building block for
+    /// merging or extending files while keeping their existing ids. Names added this way are written
+    /// to the header even if no object uses them.
     ///
-    /// * `path_str` - The path to the directory where the file will be created.
-    /// * `file_name` - The name of the file to create.
+    /// # Arguments
     ///
-    /// # Panics
+    /// * `name` - The field name to look up or add.
     ///
-    /// Will panic if the path is invalid
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// let id: u8 = builder.ensure_field("name").unwrap();
+    ///
+    /// assert_eq!(builder.ensure_field("name").unwrap(), id);
+    /// ```
     ///
     /// # Errors
     ///
-    /// Returns an error if the path is invalid
+    /// Returns an error if the name is longer than 255 bytes or if every id is already taken.
     ///
-    /// # Notes
+    /// # Panics
     ///
-    /// There is an async version of this function available if the `async` feature is enabled.
-    pub fn build_file(&mut self, path_str: &str, file_name: &str) -> Result<(), SpudError> {
-        let path_str: String = check_path(path_str, file_name)?;
-
-        let path: &Path = Path::new(&path_str);
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn ensure_field(&self, name: &str) -> Result<u8, SpudError> {
+        intern_field_name(&self.field_names, &self.seen_ids, self.deterministic, name)
+    }
 
-        fs::write(path, self.data.lock().unwrap().clone())?;
+    /// Creates a new `SpudObjectSync` instance associated with this builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure that takes a reference to the `SpudObjectSync` and returns a `Result<(), SpudError>`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     Ok(())
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `SpudObjectSync` that is linked to the builder's field names, seen IDs, and objects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object cannot be created, typically due to internal issues with the builder's state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    ///
+    /// # Note
+    ///
+    /// The `SpudObjectSync` created by this method will share the same field names, seen IDs, and objects as the builder.
+    pub fn object<F>(&self, f: F) -> Result<(), SpudError>
+    where
+        F: FnOnce(&SpudObjectSync) -> Result<(), SpudError>,
+    {
+        self.object_with_id(ObjectId::new()?, f)
+    }
+
+    /// Creates a top-level object like `object`, but with a caller-chosen id instead of a freshly generated one.
+    ///
+    /// Useful when re-encoding records that already have stable ids, e.g. from a database, so consumers
+    /// can keep correlating them. The decoder reports the id as the object's `oid`.
+    ///
+    /// # Arguments
+    ///
+    /// * `oid` - The id written for the object.
+    /// * `f` - A closure that takes a reference to the `SpudObjectSync` and returns a `Result<(), SpudError>`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder, types::ObjectId};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// let oid: ObjectId = ObjectId::from([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    ///
+    /// builder.object_with_id(oid, |obj| {
+    ///     obj.add_value("name", "Alice")?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+    ///
+    /// assert_eq!(SpudDecoder::new(&encoded_bytes).unwrap().object_ids().unwrap(), [oid]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SpudError::ValidationError` if the builder already holds a top-level object with the same id,
+    /// or an error if the object cannot be created or the closure fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn object_with_id<F>(&self, oid: ObjectId, f: F) -> Result<(), SpudError>
+    where
+        F: FnOnce(&SpudObjectSync) -> Result<(), SpudError>,
+    {
+        let start: usize = self.data.lock().unwrap().len();
+
+        let obj: Arc<Mutex<SpudObjectSync>> = self.new_object(oid)?;
+
+        f(&obj.lock().unwrap())?;
+
+        {
+            let mut data: MutexGuard<'_, Vec<u8>> = self.data.lock().unwrap();
+
+            data.push(SpudTypes::ObjectEnd.as_u8());
+            data.push(SpudTypes::ObjectEnd.as_u8());
+
+            if self.capabilities.contains(Capabilities::CHECKSUM) {
+                let checksum: u32 = crc32(&data[start..]);
+
+                data.extend_from_slice(&checksum.to_le_bytes());
+            }
+        }
+
+        let mut obj: MutexGuard<'_, SpudObjectSync> = obj.lock().unwrap();
+
+        obj.close();
+
+        let end: usize = self.data.lock().unwrap().len();
+
+        self.object_spans
+            .lock()
+            .unwrap()
+            .push((obj.oid, start..end));
+
+        Ok(())
+    }
+
+    /// Empties the builder so it can produce another, unrelated file, keeping its settings and allocations.
+    ///
+    /// The data buffer, field-name table, objects and id bookkeeping are reset to the state of a new builder,
+    /// while options such as `with_object_checksums` or `with_max_depth` stay in effect. Every object is closed
+    /// and dropped, so a handle retained from before the call rejects further writes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let mut builder = SpudBuilderSync::new();
+    ///
+    /// for batch in 0..3u8 {
+    ///     builder.object(|obj| {
+    ///         obj.add_value("batch", batch)?;
+    ///
+    ///         Ok(())
+    ///     }).unwrap();
+    ///
+    ///     let bytes: Vec<u8> = builder.build_bytes().unwrap();
+    ///
+    ///     builder.clear();
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn clear(&mut self) {
+        self.objects.lock().unwrap().clear();
+        self.data.lock().unwrap().clear();
+        self.field_names.lock().unwrap().clear();
+        self.open_arrays.lock().unwrap().clear();
+        self.object_spans.lock().unwrap().clear();
+
+        let mut seen_ids: MutexGuard<'_, Vec<bool>> = self.seen_ids.lock().unwrap();
+
+        seen_ids.fill(false);
+        seen_ids[0] = true;
+        seen_ids[1] = true;
+    }
+
+    /// Puts the field-name table in name order when the builder is deterministic, ids are left untouched.
+    fn sort_field_names(&self) {
+        if self.deterministic {
+            self.field_names.lock().unwrap().sort_keys();
+        }
+    }
+
+    /// Checks that the data buffer holds exactly the finished top-level objects, so their recorded spans can be sliced from it.
+    fn check_object_spans(data: &[u8], object_spans: &ObjectSpans) -> Result<(), SpudError> {
+        let data_len: usize = data.len();
+        let objects_len: usize = object_spans.iter().map(|(_, span)| span.len()).sum();
+
+        if data_len != objects_len {
+            return Err(SpudError::EncodingError(format!(
+                "the data buffer holds {data_len} bytes but finished objects account for {objects_len}, an object failed part way or the builder was already encoded"
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn new_object(&self, oid: ObjectId) -> Result<Arc<Mutex<SpudObjectSync>>, SpudError> {
+        let object: Arc<Mutex<SpudObjectSync>> = SpudObjectSync::new(
+            oid,
+            Arc::clone(&self.field_names),
+            Arc::clone(&self.seen_ids),
+            Arc::clone(&self.objects),
+            Arc::clone(&self.data),
+            Arc::clone(&self.open_arrays),
+            self.unique_fields,
+            self.deterministic,
+            Nesting::top_level(self.max_depth),
+        )?;
+
+        if self.capabilities.contains(Capabilities::OBJECT_VERSION) {
+            self.data.lock().unwrap().push(OBJECT_FORMAT_VERSION);
+        }
+
+        if let Some(field_name) = &self.auto_timestamp {
+            object
+                .lock()
+                .unwrap()
+                .add_value(field_name, DateTime::now()?)?;
+        }
+
+        Ok(object)
+    }
+
+    /// Runs the checks `encode` relies on without producing any output, so a large build can fail fast.
+    ///
+    /// The field-name table must fit the header, every name at most 255 bytes long with a distinct,
+    /// non-reserved id, and the data buffer must hold only finished objects. An `object()` call whose
+    /// closure failed, e.g. because the id space ran out, leaves its partial bytes behind and is reported here.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("name", "Alice")?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// assert!(builder.validate().is_ok());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EncodingError` describing the first problem found.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn validate(&self) -> Result<(), SpudError> {
+        for object in self.objects.lock().unwrap().0.values() {
+            object.lock().unwrap().encode()?;
+        }
+
+        let mut seen_ids: Vec<bool> = vec![false; 256];
+
+        seen_ids[0] = true;
+        seen_ids[1] = true;
+
+        for ((name, name_len), id) in self.field_names.lock().unwrap().iter() {
+            if u8::try_from(name.len()).ok() != Some(*name_len) {
+                return Err(SpudError::EncodingError(format!(
+                    "field name '{name}' does not fit in 255 bytes"
+                )));
+            }
+
+            if seen_ids[usize::from(*id)] {
+                return Err(SpudError::EncodingError(format!(
+                    "field id {id} of '{name}' is reserved or used more than once"
+                )));
+            }
+
+            seen_ids[usize::from(*id)] = true;
+        }
+
+        Self::check_object_spans(
+            &self.data.lock().unwrap(),
+            &self.object_spans.lock().unwrap(),
+        )
+    }
+
+    /// Encodes all objects associated with this builder into a byte vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     Ok(())
+    /// });
+    ///
+    /// let encoded_data = builder.encode().unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the objects cannot be encoded, typically due to issues with the data format or internal state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn encode(&self) -> Result<Vec<u8>, SpudError> {
+        for object in self.objects.lock().unwrap().0.values() {
+            object.lock().unwrap().encode()?;
+        }
+
+        self.sort_field_names();
+
+        let header: Vec<u8> = initialise_header_sync(
+            &self.field_names.lock().unwrap(),
+            &self.data.lock().unwrap(),
+            self.capabilities,
+        );
+
+        self.data.lock().unwrap().clear();
+        self.data.lock().unwrap().extend_from_slice(&header);
+
+        Ok(header)
+    }
+
+    /// Encodes all objects and decodes the result straight back to JSON, without touching the filesystem.
+    ///
+    /// Meant for round-trip tests, it is only available with the `test-utils` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `pretty` - Whether to format the JSON output with indentation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("val", 1u8)?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// assert!(builder.encode_and_decode(false).unwrap().contains(r#""val":1"#));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the objects cannot be encoded or the encoded file cannot be decoded.
+    #[cfg(feature = "test-utils")]
+    pub fn encode_and_decode(&self, pretty: bool) -> Result<String, SpudError> {
+        let mut decoder: SpudDecoder = SpudDecoder::from_vec(self.encode()?)?;
+
+        Ok(decoder.decode(pretty, false)?.to_owned())
+    }
+
+    /// Encodes all objects like `encode`, but writes the top-level objects sorted by a key computed from their ids.
+    ///
+    /// Every top-level object is written to the data buffer as one contiguous run of bytes, since its closure
+    /// has to return before the next object starts, so the builder records where each one lies and reorders
+    /// those runs before adding the header. The sort is stable, objects with equal keys keep their creation order.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A function mapping an object's `ObjectId` to the key it is sorted by.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::cmp::Reverse;
+    ///
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// for i in 0..3u8 {
+    ///     builder.object(|obj| {
+    ///         obj.add_value("index", i)?;
+    ///
+    ///         Ok(())
+    ///     }).unwrap();
+    /// }
+    ///
+    /// let encoded_data = builder.encode_ordered_by(|oid| Reverse(*oid)).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EncodingError` if the builder was already encoded or an object failed part way, since the
+    /// recorded object positions no longer match the data buffer, or an error if any of the objects cannot be encoded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn encode_ordered_by<F, K>(&self, key: F) -> Result<Vec<u8>, SpudError>
+    where
+        F: Fn(&ObjectId) -> K,
+        K: Ord,
+    {
+        {
+            let mut object_spans: MutexGuard<'_, ObjectSpans> = self.object_spans.lock().unwrap();
+            let mut data: MutexGuard<'_, Vec<u8>> = self.data.lock().unwrap();
+
+            Self::check_object_spans(&data, &object_spans)?;
+
+            object_spans.sort_by_key(|(oid, _)| key(oid));
+
+            let mut reordered: Vec<u8> = Vec::with_capacity(data.len());
+
+            for (_, span) in object_spans.iter_mut() {
+                let start: usize = reordered.len();
+
+                reordered.extend_from_slice(&data[span.clone()]);
+
+                *span = start..reordered.len();
+            }
+
+            *data = reordered;
+        }
+
+        self.encode()
+    }
+
+    /// Encodes all objects and returns the complete file, header and trailer included, without touching the filesystem.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let mut builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("val", 1u8)?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// let bytes: Vec<u8> = builder.build_bytes().unwrap();
+    ///
+    /// assert!(bytes.ends_with(&[0xDE, 0xAD, 0xBE, 0xEF]));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the objects cannot be encoded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn build_bytes(&mut self) -> Result<Vec<u8>, SpudError> {
+        self.encode()?;
+
+        Ok(self.data.lock().unwrap().clone())
+    }
+
+    /// Writes the complete file, header and trailer included, to a `Write` sink.
+    ///
+    /// Unlike `encode`, the file is not assembled in memory first: the header is written, then every
+    /// top-level object straight from the builder's buffer, then the trailer, so exporting a large
+    /// builder does not need room for a second copy of its data. With `with_footer_field_table` the
+    /// field-name table is written after the objects. The builder is left as it was, so it can be
+    /// written again or encoded afterwards, but once `encode` has run its buffer holds the finished
+    /// file rather than the objects, and this returns an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The sink to write the file to, e.g. a `BufWriter<File>` or a socket.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("val", 1u8)?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// let mut bytes: Vec<u8> = Vec::new();
+    ///
+    /// builder.encode_to_writer(&mut bytes).unwrap();
+    ///
+    /// assert!(SpudDecoder::new(&bytes).is_ok());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EncodingError` if the builder was already encoded or an object failed part way, or an error
+    /// if any of the objects cannot be encoded or the sink cannot be written to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn encode_to_writer<W: Write>(&self, writer: &mut W) -> Result<(), SpudError> {
+        for object in self.objects.lock().unwrap().0.values() {
+            object.lock().unwrap().encode()?;
+        }
+
+        let object_spans: MutexGuard<'_, ObjectSpans> = self.object_spans.lock().unwrap();
+        let data: MutexGuard<'_, Vec<u8>> = self.data.lock().unwrap();
+
+        Self::check_object_spans(&data, &object_spans)?;
+
+        self.sort_field_names();
+
+        let field_names: MutexGuard<'_, IndexMap<(String, u8), u8>> =
+            self.field_names.lock().unwrap();
+
+        let footer: bool = self.capabilities.contains(Capabilities::FOOTER_FIELD_TABLE);
+
+        let mut header: Vec<u8> = SPUD_VERSION.as_bytes().to_vec();
+
+        if footer {
+            header.push(SpudTypes::FieldNameListEnd.as_u8());
+        } else {
+            write_field_table(&field_names, &mut header);
+        }
+
+        writer.write_all(&header)?;
+
+        let mut written: usize = header.len();
+
+        for (_, span) in object_spans.iter() {
+            writer.write_all(&data[span.clone()])?;
+
+            written += span.len();
+        }
+
+        let mut trailer: Vec<u8> = Vec::new();
+
+        if footer {
+            write_field_footer(&field_names, written, &mut trailer);
+        }
+
+        trailer.extend_from_slice(&self.capabilities.as_trailer());
+
+        writer.write_all(&trailer)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Builds the SPUD file at the specified path with the given file name.
+    ///
+    ///  # Arguments
+    ///
+    /// * `path_str` - The path to the directory where the file will be created.
+    /// * `file_name` - The name of the file to create.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the path is invalid
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is invalid
+    ///
+    /// # Notes
+    ///
+    /// There is an async version of this function available if the `async` feature is enabled.
+    pub fn build_file(&mut self, path_str: &str, file_name: &str) -> Result<(), SpudError> {
+        let path_str: String = check_path(path_str, file_name)?;
+
+        let path: &Path = Path::new(&path_str);
+
+        fs::write(path, self.data.lock().unwrap().clone())?;
+
+        Ok(())
+    }
+
+    /// Replaces the contents of an already open file with the builder's data.
+    ///
+    /// The file is rewound and truncated before writing, so the same handle can be reused for
+    /// periodic snapshots without reopening the file each time. The builder must have been encoded first.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - A file opened for writing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::fs::File;
+    ///
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// let mut file = File::create("snapshot.spud").unwrap();
+    ///
+    /// builder.encode().unwrap();
+    /// builder.write_to_file(&mut file).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EncodingError` if the builder has not been encoded yet, or an error if the file cannot be rewound, truncated or written to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn write_to_file(&self, file: &mut fs::File) -> Result<(), SpudError> {
+        let data: MutexGuard<'_, Vec<u8>> = self.data.lock().unwrap();
+
+        if !data.starts_with(SPUD_VERSION.as_bytes()) {
+            return Err(SpudError::EncodingError(
+                "the builder has not been encoded, call encode() before write_to_file()".to_owned(),
+            ));
+        }
+
+        file.seek(SeekFrom::Start(0))?;
+        file.set_len(0)?;
+        file.write_all(&data)?;
+        file.flush()?;
 
         Ok(())
     }
@@ -206,6 +1133,13 @@ impl fmt::Debug for SpudBuilderSync {
             }
         }
         debug_builder.field("seen_ids", &seen_ids_to_display);
+        debug_builder.field("open_arrays", &self.open_arrays.lock().unwrap());
+        debug_builder.field("object_spans", &self.object_spans.lock().unwrap());
+        debug_builder.field("auto_timestamp", &self.auto_timestamp);
+        debug_builder.field("max_depth", &self.max_depth);
+        debug_builder.field("unique_fields", &self.unique_fields);
+        debug_builder.field("deterministic", &self.deterministic);
+        debug_builder.field("capabilities", &self.capabilities);
 
         debug_builder.finish()
     }
@@ -0,0 +1,102 @@
+use crate::{SpudBuilderSync, SpudError};
+
+/// Builds a SPUD file from tabular data, writing one object per row.
+///
+/// Each value is stored as a string under the field named by the header in the same column,
+/// the header names are interned once and shared by every row.
+///
+/// # Arguments
+///
+/// * `headers` - The field name of every column.
+/// * `rows` - The rows to encode, each holding one value per header.
+///
+/// # Examples
+///
+/// ```rust
+/// use spud_rs::{SpudDecoder, from_rows};
+///
+/// let rows: Vec<Vec<String>> = vec![vec!["Alice".to_owned(), "30".to_owned()]];
+///
+/// let encoded_bytes: Vec<u8> = from_rows(&["name", "age"], rows).unwrap();
+///
+/// let decoder = SpudDecoder::new(&encoded_bytes).unwrap();
+///
+/// assert_eq!(decoder.decode_to_value(false).unwrap()["age"], "30");
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a row does not have exactly one value per header, or if the objects cannot be encoded.
+pub fn from_rows<I>(headers: &[&str], rows: I) -> Result<Vec<u8>, SpudError>
+where
+    I: IntoIterator<Item = Vec<String>>,
+{
+    let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+    for (index, row) in rows.into_iter().enumerate() {
+        if row.len() != headers.len() {
+            return Err(SpudError::EncodingError(format!(
+                "row {index} has {} values but there are {} headers",
+                row.len(),
+                headers.len()
+            )));
+        }
+
+        builder.object(|obj| {
+            for (header, value) in headers.iter().zip(row) {
+                obj.add_value(header, value)?;
+            }
+
+            Ok(())
+        })?;
+    }
+
+    builder.encode()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::SpudDecoder;
+
+    fn row(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| (*value).to_owned()).collect()
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let rows: Vec<Vec<String>> = vec![
+            row(&["Alice", "Rome"]),
+            row(&["Bob", "Oslo"]),
+            row(&["Carol", "Lima"]),
+        ];
+
+        let encoded_bytes: Vec<u8> = from_rows(&["name", "city"], rows).unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        assert_eq!(decoder.field_names.len(), 2);
+
+        let objects: Vec<Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(objects.len(), 3);
+        assert_eq!(objects[0]["name"], "Alice");
+        assert_eq!(objects[0]["city"], "Rome");
+        assert_eq!(objects[1]["name"], "Bob");
+        assert_eq!(objects[1]["city"], "Oslo");
+        assert_eq!(objects[2]["name"], "Carol");
+        assert_eq!(objects[2]["city"], "Lima");
+    }
+
+    #[test]
+    fn test_from_rows_wrong_length() {
+        let rows: Vec<Vec<String>> = vec![row(&["Alice", "Rome"]), row(&["Bob"])];
+
+        assert!(matches!(
+            from_rows(&["name", "city"], rows),
+            Err(SpudError::EncodingError(message)) if message.contains("row 1")
+        ));
+    }
+}
@@ -1,8 +1,14 @@
+mod array;
 mod builder;
+mod from_rows;
 mod object;
+mod object_array;
 
+pub use array::SpudArraySync;
 pub use builder::SpudBuilderSync;
+pub use from_rows::from_rows;
 pub use object::SpudObjectSync;
+pub use object_array::SpudObjectArraySync;
 
 #[cfg(all(test, feature = "sync"))]
 mod tests {
@@ -11,7 +17,7 @@ mod tests {
     use std::sync::MutexGuard;
 
     use crate::{
-        SpudBuilderSync, SpudObjectSync,
+        SpudBuilderSync, SpudError, SpudObjectSync,
         spud_types::{SpudNumberTypes, SpudTypes},
         types::{BinaryBlob, SpudString},
     };
@@ -687,6 +693,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_spud_builder_minimal_header() {
+        use crate::{SPUD_VERSION, SpudDecoder};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder.object(|_| Ok(())).unwrap();
+
+        let object_bytes: Vec<u8> = builder.data.lock().unwrap().clone();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut expected: Vec<u8> = SPUD_VERSION.as_bytes().to_vec();
+        expected.push(SpudTypes::FieldNameListEnd.as_u8());
+        expected.extend_from_slice(&object_bytes);
+        expected.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+
+        assert_eq!(object_bytes.len(), 14);
+        assert_eq!(encoded_bytes, expected);
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        let object: &serde_json::Map<String, serde_json::Value> = output.as_object().unwrap();
+
+        assert_eq!(object.len(), 1);
+        assert!(object["oid"].is_string());
+    }
+
     #[test]
     fn test_debug_spud_builder() {
         let builder: SpudBuilderSync = SpudBuilderSync::new();
@@ -747,4 +784,896 @@ mod tests {
             .build_file("./.tmp/spud", "sync_test_with_objects")
             .unwrap();
     }
+
+    #[test]
+    fn test_spud_builder_write_to_file_replaces_contents() {
+        use std::fs::{self, File, OpenOptions};
+
+        let first: SpudBuilderSync = SpudBuilderSync::new();
+
+        first
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("long_field_name", SpudString::from("a fairly long value"))?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        first.encode().unwrap();
+
+        let second: SpudBuilderSync = SpudBuilderSync::new();
+
+        second
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("a", 1u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let second_bytes: Vec<u8> = second.encode().unwrap();
+
+        fs::create_dir_all("./.tmp/spud").unwrap();
+
+        let path: &str = "./.tmp/spud/sync_write_to_file.spud";
+
+        let mut file: File = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(path)
+            .unwrap();
+
+        first.write_to_file(&mut file).unwrap();
+        second.write_to_file(&mut file).unwrap();
+
+        assert_eq!(fs::read(path).unwrap(), second_bytes);
+    }
+
+    #[test]
+    fn test_spud_builder_write_to_file_requires_encode() {
+        use std::fs::{self, File};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("value", 1u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        fs::create_dir_all("./.tmp/spud").unwrap();
+
+        let path: &str = "./.tmp/spud/sync_write_to_file_unencoded.spud";
+
+        let mut file: File = File::create(path).unwrap();
+
+        assert!(matches!(
+            builder.write_to_file(&mut file),
+            Err(SpudError::EncodingError(_))
+        ));
+        assert!(fs::read(path).unwrap().is_empty());
+
+        builder.encode().unwrap();
+
+        assert!(builder.write_to_file(&mut file).is_ok());
+    }
+
+    #[test]
+    fn test_spud_builder_auto_timestamp() {
+        use chrono::{NaiveDateTime, Utc};
+
+        use crate::{SpudDecoder, types::DateTime};
+
+        let mut builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder.with_auto_timestamp("created_at");
+
+        for i in 0..2_u8 {
+            builder
+                .object(|obj: &SpudObjectSync| {
+                    obj.add_value("index", i)?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(objects.len(), 2);
+
+        for object in &objects {
+            let created_at: NaiveDateTime = NaiveDateTime::try_from(
+                DateTime::from_str(object["created_at"].as_str().unwrap()).unwrap(),
+            )
+            .unwrap();
+
+            let age: chrono::TimeDelta = Utc::now().naive_utc() - created_at;
+
+            assert!(age >= chrono::TimeDelta::zero() && age < chrono::TimeDelta::minutes(1));
+        }
+    }
+
+    #[test]
+    fn test_spud_builder_add_value_after_close() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::SpudError;
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("a", 1u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let handle: Arc<Mutex<SpudObjectSync>> =
+            Arc::clone(builder.objects.lock().unwrap().0.values().next().unwrap());
+
+        let Err(SpudError::EncodingError(message)) =
+            handle.lock().unwrap().add_value("b", 2u8).map(|_| ())
+        else {
+            panic!("expected an encoding error");
+        };
+
+        assert_eq!(message, "object already closed");
+    }
+
+    #[test]
+    fn test_spud_builder_encode_ordered_by_reverse() {
+        use crate::{SpudDecoder, types::ObjectId};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        for i in 0..3_u8 {
+            builder
+                .object(|obj: &SpudObjectSync| {
+                    obj.add_value("index", i)?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let creation_order: Vec<ObjectId> =
+            builder.objects.lock().unwrap().0.keys().copied().collect();
+
+        let encoded_bytes: Vec<u8> = builder
+            .encode_ordered_by(|oid| {
+                std::cmp::Reverse(creation_order.iter().position(|id| id == oid))
+            })
+            .unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let indices: Vec<serde_json::Value> = decoder
+            .decode_values()
+            .unwrap()
+            .into_iter()
+            .map(|object| object["index"].clone())
+            .collect();
+
+        assert_eq!(indices, [2, 1, 0]);
+    }
+
+    #[test]
+    fn test_spud_builder_encode_ordered_by_after_encode() {
+        use crate::SpudError;
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("index", 1u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let Err(SpudError::EncodingError(message)) = builder.encode_ordered_by(|oid| *oid) else {
+            panic!("expected an encoding error");
+        };
+
+        assert!(message.contains("already encoded"));
+        assert_eq!(*builder.data.lock().unwrap(), encoded_bytes);
+    }
+
+    #[test]
+    fn test_spud_builder_max_depth_exceeded() {
+        use crate::SpudError;
+
+        let mut builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder.with_max_depth(2);
+
+        let result: Result<(), SpudError> = builder.object(|obj: &SpudObjectSync| {
+            obj.object("child", |child: &SpudObjectSync| {
+                child.add_value("a", 1u8)?;
+
+                child.object("grandchild", |_| Ok(()))
+            })
+        });
+
+        let Err(SpudError::EncodingError(message)) = result else {
+            panic!("expected an encoding error");
+        };
+
+        assert_eq!(message, "max nesting depth exceeded");
+    }
+
+    #[test]
+    fn test_spud_builder_max_depth_within_limit() {
+        let mut builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder.with_max_depth(2);
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.object("child", |child: &SpudObjectSync| {
+                    child.add_value("a", 1u8)?;
+
+                    Ok(())
+                })
+            })
+            .unwrap();
+
+        builder.encode().unwrap();
+    }
+
+    #[test]
+    fn test_spud_builder_path_values() {
+        use std::path::{Path, PathBuf};
+
+        use crate::SpudDecoder;
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("config", Path::new("/etc/spud/config.spud"))?;
+                obj.add_value("data", PathBuf::from("/srv/données/日本語.spud"))?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(objects[0]["config"], "/etc/spud/config.spud");
+        assert_eq!(objects[0]["data"], "/srv/données/日本語.spud");
+    }
+
+    #[test]
+    fn test_spud_builder_set_field_table() {
+        use crate::SPUD_VERSION;
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .set_field_table(&[("name", 7), ("age", 42)])
+            .unwrap();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("age", 30u8)?;
+                obj.add_value("name", "Alice")?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut expected: Vec<u8> = SPUD_VERSION.as_bytes().to_vec();
+        expected.extend_from_slice(&[4, b'n', b'a', b'm', b'e', 7]);
+        expected.extend_from_slice(&[3, b'a', b'g', b'e', 42]);
+        expected.push(SpudTypes::FieldNameListEnd.as_u8());
+
+        assert!(encoded_bytes.starts_with(&expected));
+        assert_eq!(
+            encoded_bytes[expected.len() + 12..expected.len() + 14],
+            [SpudTypes::FieldNameId.as_u8(), 42]
+        );
+    }
+
+    #[test]
+    fn test_spud_builder_set_field_table_rejects_invalid_ids() {
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        assert!(builder.set_field_table(&[("name", 1)]).is_err());
+        assert!(builder.set_field_table(&[("name", 5), ("age", 5)]).is_err());
+        assert!(
+            builder
+                .set_field_table(&[("name", 5), ("name", 6)])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_spud_builder_build_bytes() {
+        use crate::SpudDecoder;
+
+        let mut builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("val", 1u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let bytes: Vec<u8> = builder.build_bytes().unwrap();
+
+        assert!(bytes.ends_with(&[0xDE, 0xAD, 0xBE, 0xEF]));
+        assert_eq!(bytes, *builder.data.lock().unwrap());
+
+        let decoder: SpudDecoder = SpudDecoder::new(&bytes).unwrap();
+
+        assert_eq!(decoder.decode_values().unwrap()[0]["val"], 1);
+    }
+
+    #[test]
+    fn test_spud_object_add_value_checked() {
+        use crate::{SpudDecoder, SpudSchema, SpudSchemaTypes};
+
+        let mut schema: SpudSchema = SpudSchema::new();
+
+        schema.add_field("name", SpudSchemaTypes::String).add_field(
+            "age",
+            SpudSchemaTypes::Optional(Box::new(SpudSchemaTypes::Number)),
+        );
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value_checked(&schema, "name", "Alice")?;
+                obj.add_value_checked(&schema, "age", ())?;
+
+                assert!(matches!(
+                    obj.add_value_checked(&schema, "name", 42u8),
+                    Err(SpudError::ValidationError(message)) if message.contains("expected String")
+                ));
+                assert!(matches!(
+                    obj.add_value_checked(&schema, "email", "alice@example.com"),
+                    Err(SpudError::ValidationError(message)) if message.contains("not declared")
+                ));
+
+                Ok(())
+            })
+            .unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&builder.encode().unwrap()).unwrap();
+
+        let object: serde_json::Value = decoder.decode_to_value(false).unwrap();
+
+        assert_eq!(object["name"], "Alice");
+        assert!(object["age"].is_null());
+        assert!(object.get("email").is_none());
+    }
+
+    #[test]
+    fn test_spud_builder_ensure_field() {
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        let name_id: u8 = builder.ensure_field("name").unwrap();
+        let age_id: u8 = builder.ensure_field("age").unwrap();
+
+        assert_eq!(builder.ensure_field("name").unwrap(), name_id);
+        assert_ne!(name_id, age_id);
+        assert!(name_id > 1 && age_id > 1);
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("name", "Alice")?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(
+            builder.field_names.lock().unwrap()[&("name".to_owned(), 4)],
+            name_id
+        );
+    }
+
+    #[test]
+    fn test_spud_builder_validate() {
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("name", "Alice")?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        builder.validate().unwrap();
+
+        let result = builder.object(|obj: &SpudObjectSync| {
+            for i in 0..255u16 {
+                obj.add_value(&format!("field_{i}"), i)?;
+            }
+
+            Ok(())
+        });
+
+        assert!(
+            matches!(result, Err(SpudError::EncodingError(message)) if message.contains("id space"))
+        );
+        assert!(matches!(
+            builder.validate(),
+            Err(SpudError::EncodingError(_))
+        ));
+    }
+
+    #[test]
+    fn test_spud_builder_encode_to_writer() {
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        for i in 0..3u8 {
+            builder
+                .object(|obj: &SpudObjectSync| {
+                    obj.add_value("index", i)?;
+                    obj.add_value("name", "Alice")?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let mut written: Vec<u8> = Vec::new();
+
+        builder.encode_to_writer(&mut written).unwrap();
+
+        assert_eq!(written, builder.encode().unwrap());
+    }
+
+    #[test]
+    fn test_spud_builder_encode_to_writer_after_encode() {
+        use crate::SpudError;
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("index", 1u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        builder.encode().unwrap();
+
+        let mut written: Vec<u8> = Vec::new();
+
+        let Err(SpudError::EncodingError(message)) = builder.encode_to_writer(&mut written) else {
+            panic!("expected an encoding error");
+        };
+
+        assert!(message.contains("already encoded"));
+        assert!(written.is_empty());
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_spud_builder_encode_and_decode() {
+        use crate::types::{BinaryBlob, Date, Decimal};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("name", "Alice")?;
+                obj.add_value("age", 30u8)?;
+                obj.add_value("balance", Decimal::new(1050, 2))?;
+                obj.add_value("born", Date::new(1990, 5, 17)?)?;
+                obj.add_value("active", true)?;
+                obj.add_value("avatar", BinaryBlob::new(&[1, 2, 3]))?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(&builder.encode_and_decode(true).unwrap()).unwrap();
+
+        assert_eq!(output["name"], "Alice");
+        assert_eq!(output["age"], 30);
+        assert_eq!(output["balance"], "10.50");
+        assert_eq!(output["born"], "1990-05-17");
+        assert_eq!(output["active"], true);
+        assert_eq!(output["avatar"], serde_json::json!([1, 2, 3]));
+    }
+
+    #[test]
+    fn test_spud_builder_object_array() {
+        use crate::{SpudDecoder, SpudObjectArraySync};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_object_array("users", |arr: &SpudObjectArraySync| {
+                    arr.push_object(|user| {
+                        user.add_value("name", "Alice")?;
+                        user.add_value("age", 30u8)?;
+
+                        Ok(())
+                    })?
+                    .push_object(|user| {
+                        user.add_value("name", "Bob")?;
+                        user.add_value("age", 25u8)?;
+
+                        Ok(())
+                    })?;
+
+                    Ok(())
+                })?;
+                obj.add_value("count", 2u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let object: serde_json::Value = decoder.decode_values().unwrap().remove(0);
+
+        let users: &Vec<serde_json::Value> = object["users"].as_array().unwrap();
+
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0]["name"], "Alice");
+        assert_eq!(users[0]["age"], 30);
+        assert_eq!(users[1]["name"], "Bob");
+        assert_eq!(users[1]["age"], 25);
+        assert_ne!(users[0]["oid"], users[1]["oid"]);
+        assert_eq!(object["count"], 2);
+    }
+
+    #[test]
+    fn test_spud_builder_unique_fields() {
+        let mut builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder.with_unique_fields();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("x", 1u8)?;
+                obj.append_to_array("tags", &[1u8])?;
+                obj.append_to_array("tags", &[2u8])?;
+                obj.object("nested", |nested: &SpudObjectSync| {
+                    nested.add_value("x", 2u8)?;
+
+                    Ok(())
+                })?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let result: Result<(), SpudError> = builder.object(|obj: &SpudObjectSync| {
+            obj.add_value("x", 1u8)?;
+            obj.add_value("x", 2u8)?;
+
+            Ok(())
+        });
+
+        let Err(SpudError::ValidationError(message)) = result else {
+            panic!("expected a validation error");
+        };
+
+        assert_eq!(message, "field 'x' is already set on this object");
+    }
+
+    #[test]
+    fn test_spud_builder_raw_typed() {
+        use crate::SpudDecoder;
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_raw_typed_unchecked(
+                    "port",
+                    SpudTypes::Number(SpudNumberTypes::U16).as_u8(),
+                    &8080u16.to_le_bytes(),
+                )?;
+                obj.add_value("name", "web")?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(objects[0]["port"], 8080);
+        assert_eq!(objects[0]["name"], "web");
+    }
+
+    #[test]
+    fn test_spud_builder_map() {
+        use indexmap::IndexMap;
+
+        use crate::SpudDecoder;
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        let mut scores: IndexMap<String, u32> = IndexMap::new();
+
+        scores.insert("alice".to_owned(), 42);
+        scores.insert("bob".to_owned(), 7);
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_map("scores", &scores)?;
+                obj.add_map("empty", &IndexMap::<String, u8>::new())?;
+                obj.add_value("alice", "not a score")?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(objects[0]["scores"]["alice"], 42);
+        assert_eq!(objects[0]["scores"]["bob"], 7);
+        assert!(objects[0]["empty"].as_object().unwrap().keys().eq(["oid"]));
+        assert_eq!(objects[0]["alice"], "not a score");
+    }
+
+    #[test]
+    fn test_spud_builder_deterministic() {
+        let build = || {
+            let builder: SpudBuilderSync = SpudBuilderSync::new_deterministic();
+
+            builder
+                .object(|obj: &SpudObjectSync| {
+                    obj.add_value("zeta", 1u8)?;
+                    obj.add_value("alpha", "a")?;
+                    obj.object("inner", |inner: &SpudObjectSync| {
+                        inner.add_value("zeta", 2u8)?;
+
+                        Ok(())
+                    })?;
+
+                    Ok(())
+                })
+                .unwrap();
+
+            let mut encoded: Vec<u8> = builder.encode().unwrap();
+
+            // Object ids are time-based, blank out the 10 bytes after every `ObjectStart ObjectStart` before comparing
+            let object_start: u8 = SpudTypes::ObjectStart.as_u8();
+
+            for start in 0..encoded.len() - 1 {
+                if encoded[start..start + 2] == [object_start, object_start] {
+                    encoded[start + 2..start + 12].fill(0);
+                }
+            }
+
+            (builder, encoded)
+        };
+
+        let (builder, first) = build();
+        let (_, second) = build();
+
+        assert_eq!(first, second);
+
+        let field_names: Vec<(String, u8)> = builder
+            .field_names
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|((name, _), id)| (name.clone(), *id))
+            .collect();
+
+        assert_eq!(
+            field_names,
+            [
+                ("alpha".to_owned(), 3),
+                ("inner".to_owned(), 4),
+                ("zeta".to_owned(), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spud_builder_field_name_too_long() {
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        // 128 two-byte characters, 256 bytes
+        let field_name: String = "é".repeat(128);
+
+        let result: Result<(), SpudError> = builder.object(|obj: &SpudObjectSync| {
+            obj.add_value(&field_name, 1u8)?;
+
+            Ok(())
+        });
+
+        let Err(SpudError::ValidationError(message)) = result else {
+            panic!("expected a validation error");
+        };
+
+        assert_eq!(
+            message,
+            format!("Field name '{field_name}' exceeds 255 bytes")
+        );
+
+        assert!(
+            builder
+                .object(|obj: &SpudObjectSync| {
+                    obj.add_value(&"a".repeat(255), 1u8)?;
+
+                    Ok(())
+                })
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_spud_builder_object_with_id() {
+        use crate::{SpudDecoder, types::ObjectId};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        let oid: ObjectId = ObjectId::from([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let nested_oid: ObjectId = ObjectId::from([9; 10]);
+
+        builder
+            .object_with_id(oid, |obj: &SpudObjectSync| {
+                obj.object_with_id("nested", nested_oid, |nested: &SpudObjectSync| {
+                    nested.add_value("value", 1u8)?;
+
+                    Ok(())
+                })?;
+
+                let duplicate: Result<(), SpudError> =
+                    obj.object_with_id("again", nested_oid, |_| Ok(()));
+
+                assert!(matches!(duplicate, Err(SpudError::ValidationError(_))));
+
+                Ok(())
+            })
+            .unwrap();
+
+        let duplicate: Result<(), SpudError> = builder.object_with_id(oid, |_| Ok(()));
+
+        let Err(SpudError::ValidationError(message)) = duplicate else {
+            panic!("expected a validation error");
+        };
+
+        assert_eq!(message, format!("object id {oid} is already used"));
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        assert_eq!(decoder.object_ids().unwrap(), [oid]);
+
+        let objects: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(objects[0]["oid"], oid.to_string());
+        assert_eq!(objects[0]["nested"]["oid"], nested_oid.to_string());
+        assert_eq!(objects[0]["nested"]["value"], 1);
+    }
+
+    #[test]
+    fn test_spud_builder_clear() {
+        use std::sync::Arc;
+
+        use crate::{SpudDecoder, types::ObjectId};
+
+        let mut builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        let oid: ObjectId = ObjectId::from([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        builder
+            .object_with_id(oid, |obj: &SpudObjectSync| {
+                obj.add_value("first", 1u8)?;
+                obj.object("nested", |nested: &SpudObjectSync| {
+                    nested.add_value("value", 2u8)?;
+
+                    Ok(())
+                })?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        builder.encode().unwrap();
+
+        assert!(Arc::strong_count(&builder.data) > 1);
+
+        builder.clear();
+
+        // Every object, nested ones included, has been dropped along with its handles
+        assert_eq!(Arc::strong_count(&builder.data), 1);
+        assert_eq!(Arc::strong_count(&builder.objects), 1);
+        assert!(builder.field_names.lock().unwrap().is_empty());
+        assert_eq!(
+            builder
+                .seen_ids
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|seen| **seen)
+                .count(),
+            2
+        );
+
+        builder
+            .object_with_id(oid, |obj: &SpudObjectSync| {
+                obj.add_value("second", 3u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let objects: Vec<serde_json::Value> = SpudDecoder::new(&encoded_bytes)
+            .unwrap()
+            .decode_values()
+            .unwrap();
+
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0]["second"], 3);
+        assert!(objects[0].get("first").is_none());
+    }
+
+    #[test]
+    fn test_spud_builder_with_capacity() {
+        let mut builder: SpudBuilderSync = SpudBuilderSync::with_capacity(4096);
+
+        assert!(builder.data.lock().unwrap().capacity() >= 4096);
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("value", 1u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let data_ptr: *const u8 = builder.data.lock().unwrap().as_ptr();
+
+        builder.encode().unwrap();
+
+        // The header fits in the reserved space, so encoding did not move the buffer
+        assert_eq!(builder.data.lock().unwrap().as_ptr(), data_ptr);
+
+        builder.clear();
+
+        assert!(builder.data.lock().unwrap().capacity() >= 4096);
+    }
 }
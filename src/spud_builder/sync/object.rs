@@ -4,43 +4,72 @@ use indexmap::{IndexMap, map::Values};
 use std::sync::{Arc, Mutex, MutexGuard};
 
 use crate::{
-    SpudError, functions::generate_u8_id_sync, spud_builder::spud_type_ext::SpudTypesExt,
-    spud_types::SpudTypes, types::ObjectId,
+    SpudError, SpudSchema,
+    functions::{field_name_len, generate_u8_id_sync, next_u8_id},
+    spud_builder::{Nesting, spud_type_ext::SpudTypesExt},
+    spud_types::SpudTypes,
+    types::ObjectId,
 };
 
-use super::builder::ObjectMap;
+use super::{
+    SpudArraySync, SpudObjectArraySync,
+    builder::{ObjectMap, OpenArrays},
+};
 
 /// Represents a SPUD object, which is a collection of fields and values.
 /// It allows adding values to fields and manages the internal data structure for SPUD encoding.
 #[derive(Debug)]
 pub struct SpudObjectSync {
-    pub(crate) _oid: ObjectId,
+    pub(crate) oid: ObjectId,
     data: Arc<Mutex<Vec<u8>>>,
     field_names: Arc<Mutex<IndexMap<(String, u8), u8>>>,
     seen_ids: Arc<Mutex<Vec<bool>>>,
     objects: Arc<Mutex<ObjectMap>>,
+    open_arrays: Arc<Mutex<OpenArrays>>,
+    /// The ids of the fields written so far, tracked only when the builder rejects duplicate fields.
+    written_fields: Option<Mutex<Vec<u8>>>,
+    /// Whether new field names take the lowest free id instead of a random one.
+    sequential_ids: bool,
+    closed: bool,
+    nesting: Nesting,
 }
 
 impl SpudObjectSync {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
+        oid: ObjectId,
         field_names: Arc<Mutex<IndexMap<(String, u8), u8>>>,
         seen_ids: Arc<Mutex<Vec<bool>>>,
         objects: Arc<Mutex<ObjectMap>>,
         data: Arc<Mutex<Vec<u8>>>,
+        open_arrays: Arc<Mutex<OpenArrays>>,
+        unique_fields: bool,
+        sequential_ids: bool,
+        nesting: Nesting,
     ) -> Result<Arc<Mutex<SpudObjectSync>>, SpudError> {
+        if objects.lock().unwrap().0.contains_key(&oid) {
+            return Err(SpudError::ValidationError(format!(
+                "object id {oid} is already used"
+            )));
+        }
+
         data.lock().unwrap().extend_from_slice(&[
             SpudTypes::ObjectStart.as_u8(),
             SpudTypes::ObjectStart.as_u8(),
         ]);
-
-        let oid: ObjectId = Self::generate_oid(&mut data.lock().unwrap())?;
+        data.lock().unwrap().extend_from_slice(oid.as_bytes());
 
         let object: Arc<Mutex<SpudObjectSync>> = Arc::new(Mutex::new(Self {
-            _oid: oid,
+            oid,
             data,
             field_names,
             seen_ids,
             objects: Arc::new(Mutex::new(ObjectMap(IndexMap::new()))),
+            open_arrays,
+            written_fields: unique_fields.then(|| Mutex::new(Vec::new())),
+            sequential_ids,
+            closed: false,
+            nesting,
         }));
 
         objects.lock().unwrap().0.insert(oid, Arc::clone(&object));
@@ -57,9 +86,9 @@ impl SpudObjectSync {
     /// # Examples
     ///
     /// ```rust
-    /// use spud_rs::{SpudBuilder, SpudObjectSync, types::SpudString};
+    /// use spud_rs::{SpudBuilderSync, SpudObjectSync, types::SpudString};
     ///
-    /// let builder = SpudBuilder::new();
+    /// let builder = SpudBuilderSync::new();
     ///
     /// builder.object(|obj| {
     ///     obj.add_value("example_field", SpudString::from("example_value"));
@@ -92,6 +121,190 @@ impl SpudObjectSync {
         Ok(self)
     }
 
+    /// Adds a field holding a type byte and payload written verbatim, for layouts the typed API does not cover.
+    ///
+    /// Nothing checks that `tag` is a known type or that `payload` has the length and layout that type expects.
+    /// A mismatch is not caught here but makes the file undecodable from this field on.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field to which the value will be added.
+    /// * `tag` - The type byte written before the payload.
+    /// * `payload` - The value bytes, written as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// // 0x0A is the U16 tag, followed by its little-endian payload
+    /// builder.object(|obj| {
+    ///     obj.add_raw_typed_unchecked("port", 0x0A, &8080u16.to_le_bytes())?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A reference to the `SpudObjectSync`, allowing for method chaining.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the field name cannot be added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn add_raw_typed_unchecked(
+        &self,
+        field_name: &str,
+        tag: u8,
+        payload: &[u8],
+    ) -> Result<&Self, SpudError> {
+        self.add_field_name(field_name)?;
+
+        let mut data: MutexGuard<'_, Vec<u8>> = self.data.lock().unwrap();
+
+        data.push(tag);
+        data.extend_from_slice(payload);
+
+        Ok(self)
+    }
+
+    /// Adds a value to the object like `add_value`, after checking it against a schema.
+    ///
+    /// The field has to be declared in the schema, with a type the value's SPUD type satisfies.
+    /// Nothing is written when the check fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema` - The schema the field is checked against.
+    /// * `field_name` - The name of the field to add.
+    /// * `value` - The value to add, which must implement the `SpudTypesExt` trait.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::{SpudBuilderSync, SpudSchema, SpudSchemaTypes};
+    ///
+    /// let mut schema = SpudSchema::new();
+    ///
+    /// schema.add_field("age", SpudSchemaTypes::Number);
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value_checked(&schema, "age", 30u8)?;
+    ///
+    ///     assert!(obj.add_value_checked(&schema, "age", "thirty").is_err());
+    ///     assert!(obj.add_value_checked(&schema, "name", "Alice").is_err());
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudObjectSync`, allowing for method chaining.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SpudError::ValidationError` if the field is not declared in the schema or has another type,
+    /// or the same errors as `add_value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn add_value_checked<T: SpudTypesExt>(
+        &self,
+        schema: &SpudSchema,
+        field_name: &str,
+        value: T,
+    ) -> Result<&Self, SpudError> {
+        let mut value_bytes: Vec<u8> = Vec::new();
+
+        value.write_spud_bytes(&mut value_bytes);
+
+        if let Some(found) = value_bytes
+            .first()
+            .and_then(|&byte| SpudTypes::from_u8(byte))
+        {
+            schema.check_field(field_name, found)?;
+        }
+
+        self.add_field_name(field_name)?;
+
+        self.data.lock().unwrap().extend_from_slice(&value_bytes);
+
+        Ok(self)
+    }
+
+    /// Adds an enum value, stored as a single byte referring to the variant's name.
+    ///
+    /// Every variant name is interned in the header's field-name table, the same way field names are,
+    /// so each value only costs its tag and one byte, and decodes to the variant name as a string.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field to which the value will be added.
+    /// * `variant` - The variant to store, which must be one of `variants`.
+    /// * `variants` - Every variant of the enum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_enum("status", "active", &["active", "suspended", "closed"])?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudObjectSync`, allowing for method chaining.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `variant` is not one of `variants`, if a name is longer than 255 bytes,
+    /// or if the field-name table has no ids left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn add_enum(
+        &self,
+        field_name: &str,
+        variant: &str,
+        variants: &[&str],
+    ) -> Result<&Self, SpudError> {
+        if !variants.contains(&variant) {
+            return Err(SpudError::EncodingError(format!(
+                "unknown enum variant '{variant}'"
+            )));
+        }
+
+        self.add_field_name(field_name)?;
+
+        for name in variants {
+            self.intern(name)?;
+        }
+
+        let id: u8 = self.intern(variant)?;
+
+        self.data.lock().unwrap().push(SpudTypes::Enum.as_u8());
+        self.data.lock().unwrap().push(id);
+
+        Ok(self)
+    }
+
     /// Creates a new `SpudObjectSync` instance associated with this Object.
     ///
     /// # Errors
@@ -105,24 +318,336 @@ impl SpudObjectSync {
     where
         F: FnOnce(&SpudObjectSync) -> Result<(), SpudError>,
     {
+        let nesting: Nesting = self.nesting.nested()?;
+
         self.add_field_name(field_name)?;
 
-        let obj: Arc<Mutex<SpudObjectSync>> = self.new_object()?;
+        self.write_object(nesting, ObjectId::new()?, f)
+    }
+
+    /// Adds a nested object field like `object`, but with a caller-chosen id instead of a freshly generated one.
+    ///
+    /// Useful when re-encoding records that already have stable ids, so consumers can keep correlating them.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field to which the object will be added.
+    /// * `oid` - The id written for the nested object.
+    /// * `f` - A closure that fills in the nested object.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::{SpudBuilderSync, types::ObjectId};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// let oid: ObjectId = ObjectId::from([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    ///
+    /// builder.object(|obj| {
+    ///     obj.object_with_id("address", oid, |address| {
+    ///         address.add_value("city", "Paris")?;
+    ///
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SpudError::ValidationError` if this object already holds a nested object with the same id,
+    /// or an error if the object cannot be created or the closure fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn object_with_id<F>(&self, field_name: &str, oid: ObjectId, f: F) -> Result<(), SpudError>
+    where
+        F: FnOnce(&SpudObjectSync) -> Result<(), SpudError>,
+    {
+        let nesting: Nesting = self.nesting.nested()?;
+
+        self.add_field_name(field_name)?;
+
+        self.write_object(nesting, oid, f)
+    }
+
+    /// Adds a nested object field holding one field per entry of `map`, in the map's order.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field to which the object will be added.
+    /// * `map` - The entries to write, keyed by field name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use indexmap::IndexMap;
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// let mut scores: IndexMap<String, u32> = IndexMap::new();
+    /// scores.insert("alice".to_owned(), 42);
+    /// scores.insert("bob".to_owned(), 7);
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_map("scores", &scores)?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object would exceed the builder's max nesting depth or if a field name,
+    /// including one of the keys, cannot be added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn add_map<V: SpudTypesExt>(
+        &self,
+        field_name: &str,
+        map: &IndexMap<String, V>,
+    ) -> Result<(), SpudError> {
+        self.object(field_name, |obj: &SpudObjectSync| {
+            for (key, value) in map {
+                obj.add_field_name(key)?;
+
+                value.write_spud_bytes(&mut obj.data.lock().unwrap());
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Adds an array field whose elements are objects, each one built through the provided `SpudObjectArraySync`.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field to which the array will be added.
+    /// * `f` - A closure that pushes the objects through the provided `SpudObjectArraySync`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::{SpudBuilderSync, SpudObjectArraySync};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_object_array("users", |arr: &SpudObjectArraySync| {
+    ///         arr.push_object(|user| {
+    ///             user.add_value("name", "Alice")?;
+    ///
+    ///             Ok(())
+    ///         })?;
+    ///
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the objects would exceed the builder's max nesting depth,
+    /// if the field name cannot be added or if the closure fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn add_object_array<F>(&self, field_name: &str, f: F) -> Result<(), SpudError>
+    where
+        F: FnOnce(&SpudObjectArraySync) -> Result<(), SpudError>,
+    {
+        let nesting: Nesting = self.nesting.nested()?;
+
+        self.add_field_name(field_name)?;
+
+        self.data
+            .lock()
+            .unwrap()
+            .push(SpudTypes::ArrayStart.as_u8());
+
+        f(&SpudObjectArraySync::new(self, nesting))?;
+
+        self.data.lock().unwrap().push(SpudTypes::ArrayEnd.as_u8());
+
+        Ok(())
+    }
+
+    /// Adds an array field whose elements are written straight into the data buffer as they are pushed.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field to which the array will be added.
+    /// * `f` - A closure that pushes the elements through the provided `SpudArraySync`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::{SpudBuilderSync, SpudArraySync};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.array("readings", |arr: &SpudArraySync| {
+    ///         arr.extend((0..1_000).map(|i: u32| i * 2))?;
+    ///
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the field name cannot be added or if the closure fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn array<F>(&self, field_name: &str, f: F) -> Result<(), SpudError>
+    where
+        F: FnOnce(&SpudArraySync) -> Result<(), SpudError>,
+    {
+        self.add_field_name(field_name)?;
+
+        self.data
+            .lock()
+            .unwrap()
+            .push(SpudTypes::ArrayStart.as_u8());
+
+        f(&SpudArraySync::new(Arc::clone(&self.data)))?;
+
+        self.data.lock().unwrap().push(SpudTypes::ArrayEnd.as_u8());
+
+        Ok(())
+    }
+
+    /// Appends values to an array field, opening the array on the first call for this field.
+    ///
+    /// Later calls for the same field insert their values just before the array's closing byte,
+    /// so the elements of every call end up in one array even when other fields were written in between.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the array field.
+    /// * `values` - The values to be appended, which must implement the `SpudTypesExt` trait.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use spud_rs::SpudBuilderSync;
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.append_to_array("tags", &[1u8, 2u8])?;
+    ///     obj.add_value("name", 5u8)?;
+    ///     obj.append_to_array("tags", &[3u8])?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// A reference to the `SpudObjectSync`, allowing for method chaining.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the field name cannot be added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn append_to_array<T: SpudTypesExt>(
+        &self,
+        field_name: &str,
+        values: &[T],
+    ) -> Result<&Self, SpudError> {
+        self.ensure_open()?;
+
+        let mut encoded: Vec<u8> = Vec::new();
+
+        for value in values {
+            value.write_spud_bytes(&mut encoded);
+        }
+
+        let key: (ObjectId, String) = (self.oid, field_name.into());
+
+        let end: Option<usize> = self.open_arrays.lock().unwrap().get(&key).copied();
+
+        if let Some(end) = end {
+            let inserted: usize = encoded.len();
+
+            self.data.lock().unwrap().splice(end..end, encoded);
+
+            for position in self.open_arrays.lock().unwrap().values_mut() {
+                if *position >= end {
+                    *position += inserted;
+                }
+            }
+        } else {
+            self.add_field_name(field_name)?;
+
+            let mut data: MutexGuard<'_, Vec<u8>> = self.data.lock().unwrap();
+
+            data.push(SpudTypes::ArrayStart.as_u8());
+            data.extend_from_slice(&encoded);
+
+            self.open_arrays.lock().unwrap().insert(key, data.len());
+
+            data.push(SpudTypes::ArrayEnd.as_u8());
+        }
+
+        Ok(self)
+    }
+
+    /// Writes a nested object at `nesting` into the data buffer, letting `f` fill in its fields.
+    pub(crate) fn write_object<F>(
+        &self,
+        nesting: Nesting,
+        oid: ObjectId,
+        f: F,
+    ) -> Result<(), SpudError>
+    where
+        F: FnOnce(&SpudObjectSync) -> Result<(), SpudError>,
+    {
+        let obj: Arc<Mutex<SpudObjectSync>> = self.new_object(nesting, oid)?;
 
         f(&obj.lock().unwrap())?;
 
         self.data.lock().unwrap().push(SpudTypes::ObjectEnd.as_u8());
         self.data.lock().unwrap().push(SpudTypes::ObjectEnd.as_u8());
 
+        obj.lock().unwrap().close();
+
         Ok(())
     }
 
-    fn new_object(&self) -> Result<Arc<Mutex<SpudObjectSync>>, SpudError> {
+    fn new_object(
+        &self,
+        nesting: Nesting,
+        oid: ObjectId,
+    ) -> Result<Arc<Mutex<SpudObjectSync>>, SpudError> {
         SpudObjectSync::new(
+            oid,
             Arc::clone(&self.field_names),
             Arc::clone(&self.seen_ids),
             Arc::clone(&self.objects),
             Arc::clone(&self.data),
+            Arc::clone(&self.open_arrays),
+            self.written_fields.is_some(),
+            self.sequential_ids,
+            nesting,
         )
     }
 
@@ -137,17 +662,44 @@ impl SpudObjectSync {
         Ok(())
     }
 
+    /// Marks the object as finished, any later write through a retained handle is rejected.
+    pub(crate) fn close(&mut self) {
+        self.closed = true;
+    }
+
+    /// Closes the object and drops its nested objects, each of which holds a handle to this object's map.
+    pub(crate) fn release(&mut self) {
+        self.close();
+
+        for (_, object) in self.objects.lock().unwrap().0.drain(..) {
+            object.lock().unwrap().release();
+        }
+    }
+
+    fn ensure_open(&self) -> Result<(), SpudError> {
+        if self.closed {
+            return Err(SpudError::EncodingError("object already closed".to_owned()));
+        }
+
+        Ok(())
+    }
+
     fn add_field_name(&self, field_name: &str) -> Result<&Self, SpudError> {
-        let key: (String, u8) = (field_name.into(), u8::try_from(field_name.len())?);
+        self.ensure_open()?;
 
-        let id: u8 = if let Some(value) = self.field_names.lock().unwrap().get(&key) {
-            *value
-        } else {
-            let id: u8 = generate_u8_id_sync(&mut self.seen_ids.lock().unwrap())?;
+        let id: u8 = self.intern(field_name)?;
+
+        if let Some(written_fields) = &self.written_fields {
+            let mut written_fields: MutexGuard<'_, Vec<u8>> = written_fields.lock().unwrap();
 
-            self.field_names.lock().unwrap().insert(key, id);
-            id
-        };
+            if written_fields.contains(&id) {
+                return Err(SpudError::ValidationError(format!(
+                    "field '{field_name}' is already set on this object"
+                )));
+            }
+
+            written_fields.push(id);
+        }
 
         self.data
             .lock()
@@ -158,11 +710,33 @@ impl SpudObjectSync {
         Ok(self)
     }
 
-    fn generate_oid(data: &mut Vec<u8>) -> Result<ObjectId, SpudError> {
-        let oid: ObjectId = ObjectId::new()?;
+    fn intern(&self, name: &str) -> Result<u8, SpudError> {
+        intern_field_name(&self.field_names, &self.seen_ids, self.sequential_ids, name)
+    }
+}
 
-        data.extend_from_slice(oid.as_bytes());
+/// Returns the id `name` has in the field-name table, adding it with a fresh id first if needed.
+///
+/// Fresh ids are random unless `sequential_ids` is set, in which case the lowest free id is taken.
+pub(crate) fn intern_field_name(
+    field_names: &Mutex<IndexMap<(String, u8), u8>>,
+    seen_ids: &Mutex<Vec<bool>>,
+    sequential_ids: bool,
+    name: &str,
+) -> Result<u8, SpudError> {
+    let key: (String, u8) = (name.into(), field_name_len(name)?);
 
-        Ok(oid)
+    if let Some(value) = field_names.lock().unwrap().get(&key) {
+        return Ok(*value);
     }
+
+    let id: u8 = if sequential_ids {
+        next_u8_id(&mut seen_ids.lock().unwrap())?
+    } else {
+        generate_u8_id_sync(&mut seen_ids.lock().unwrap())?
+    };
+
+    field_names.lock().unwrap().insert(key, id);
+
+    Ok(id)
 }
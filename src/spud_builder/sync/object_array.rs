@@ -0,0 +1,44 @@
+use crate::{SpudError, spud_builder::Nesting, types::ObjectId};
+
+use super::SpudObjectSync;
+
+/// Represents a SPUD array of objects that is being built in place.
+/// Every pushed object is written straight into the builder's data buffer, right after the previous one.
+#[derive(Debug)]
+pub struct SpudObjectArraySync<'a> {
+    parent: &'a SpudObjectSync,
+    nesting: Nesting,
+}
+
+impl<'a> SpudObjectArraySync<'a> {
+    pub(crate) fn new(parent: &'a SpudObjectSync, nesting: Nesting) -> Self {
+        Self { parent, nesting }
+    }
+
+    /// Appends an object to the array.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure that adds the object's fields through the provided `SpudObjectSync`.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the `SpudObjectArraySync`, allowing for method chaining.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object cannot be created or if the closure fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex cannot be locked, which is unlikely but can happen in case of a deadlock or other synchronization issues.
+    pub fn push_object<F>(&self, f: F) -> Result<&Self, SpudError>
+    where
+        F: FnOnce(&SpudObjectSync) -> Result<(), SpudError>,
+    {
+        self.parent
+            .write_object(self.nesting, ObjectId::new()?, f)?;
+
+        Ok(self)
+    }
+}
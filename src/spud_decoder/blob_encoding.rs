@@ -0,0 +1,74 @@
+use serde_json::{Number, Value};
+
+use crate::types::BinaryBlob;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// How binary blobs are rendered in the decoded JSON.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobEncoding {
+    /// An array of byte values, e.g. `[104, 105]`.
+    #[default]
+    Array,
+    /// A padded, standard alphabet base64 string, e.g. `"aGk="`.
+    Base64,
+    /// A lowercase hex string, e.g. `"6869"`.
+    Hex,
+}
+
+impl BlobEncoding {
+    pub(crate) fn render(self, bytes: &[u8]) -> Value {
+        match self {
+            Self::Array => Value::Array(
+                bytes
+                    .iter()
+                    .map(|byte| Value::Number(Number::from(*byte)))
+                    .collect(),
+            ),
+            Self::Base64 => Value::String(base64(bytes)),
+            Self::Hex => Value::String(BinaryBlob::new(bytes).to_string()),
+        }
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut output: String = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let group: u32 = chunk.iter().enumerate().fold(0, |group, (i, byte)| {
+            group | u32::from(*byte) << (16 - 8 * i)
+        });
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let sextet: u32 = (group >> (18 - 6 * i)) & 0x3F;
+
+                output.push(char::from(BASE64_ALPHABET[sextet as usize]));
+            } else {
+                output.push('=');
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_padding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_render_hex() {
+        assert_eq!(BlobEncoding::Hex.render(&[0, 15, 255]), "000fff");
+    }
+}
@@ -0,0 +1,233 @@
+use indexmap::IndexMap;
+use serde_json::{Map, Number, Value};
+
+use crate::SpudError;
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+pub(crate) const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+
+/// Additional information values announcing an argument in the following 1, 2, 4 or 8 bytes.
+const ARGUMENT_U8: u8 = 0x18;
+const ARGUMENT_U16: u8 = 0x19;
+const ARGUMENT_U32: u8 = 0x1A;
+const ARGUMENT_U64: u8 = 0x1B;
+
+const TAG_POSITIVE_BIGNUM: u64 = 2;
+const TAG_NEGATIVE_BIGNUM: u64 = 3;
+
+const FALSE: u8 = 0xF4;
+const TRUE: u8 = 0xF5;
+const NULL: u8 = 0xF6;
+const FLOAT32: u8 = 0xFA;
+const FLOAT64: u8 = 0xFB;
+
+/// Appends a top-level object decoded with `annotate_types` to `out` as a CBOR map.
+pub(crate) fn write_object(
+    object: &IndexMap<String, Value>,
+    out: &mut Vec<u8>,
+) -> Result<(), SpudError> {
+    write_head(MAJOR_MAP, object.len() as u64, out);
+
+    for (name, value) in object {
+        write_text(name, out);
+        write_annotated(value, out)?;
+    }
+
+    Ok(())
+}
+
+/// Appends the head of a CBOR data item, its major type and argument, in the shortest form.
+// Every narrowing cast is bounded by its match arm
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn write_head(major: u8, argument: u64, out: &mut Vec<u8>) {
+    let major: u8 = major << 5;
+
+    match argument {
+        0..=23 => out.push(major | argument as u8),
+        24..=0xFF => out.extend_from_slice(&[major | ARGUMENT_U8, argument as u8]),
+        0x100..=0xFFFF => {
+            out.push(major | ARGUMENT_U16);
+            out.extend_from_slice(&(argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xFFFF_FFFF => {
+            out.push(major | ARGUMENT_U32);
+            out.extend_from_slice(&(argument as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | ARGUMENT_U64);
+            out.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+}
+
+/// Writes a `{ "type": ..., "value": ... }` pair using the SPUD type to pick the CBOR encoding,
+/// anything else, such as an `oid`, is written as plain JSON.
+fn write_annotated(value: &Value, out: &mut Vec<u8>) -> Result<(), SpudError> {
+    let (Some(Value::String(spud_type)), Some(value)) = (value.get("type"), value.get("value"))
+    else {
+        return write_json(value, out);
+    };
+
+    match (spud_type.as_str(), value) {
+        ("F32", Value::Number(number)) => {
+            let number: f64 = number.as_f64().unwrap_or(f64::NAN);
+
+            out.push(FLOAT32);
+            // The value was an `f32` to begin with, so narrowing it back is exact
+            #[allow(clippy::cast_possible_truncation)]
+            out.extend_from_slice(&(number as f32).to_be_bytes());
+        }
+        ("F64", Value::Number(number)) => {
+            out.push(FLOAT64);
+            out.extend_from_slice(&number.as_f64().unwrap_or(f64::NAN).to_be_bytes());
+        }
+        ("BinaryBlob", Value::Array(bytes)) => {
+            let bytes: Vec<u8> = bytes
+                .iter()
+                .map(|byte| byte.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+                .collect::<Option<_>>()
+                .ok_or_else(|| SpudError::DecodingError("Invalid BinaryBlob bytes".to_owned()))?;
+
+            write_head(MAJOR_BYTES, bytes.len() as u64, out);
+            out.extend_from_slice(&bytes);
+        }
+        ("Array", Value::Array(items)) => {
+            write_head(MAJOR_ARRAY, items.len() as u64, out);
+
+            for item in items {
+                write_annotated(item, out)?;
+            }
+        }
+        ("Object", Value::Object(fields)) => write_map(fields, out, write_annotated)?,
+        _ => write_json(value, out)?,
+    }
+
+    Ok(())
+}
+
+fn write_json(value: &Value, out: &mut Vec<u8>) -> Result<(), SpudError> {
+    match value {
+        Value::Null => out.push(NULL),
+        Value::Bool(value) => out.push(if *value { TRUE } else { FALSE }),
+        Value::Number(number) => write_number(number, out),
+        Value::String(text) => write_text(text, out),
+        Value::Array(items) => {
+            write_head(MAJOR_ARRAY, items.len() as u64, out);
+
+            for item in items {
+                write_json(item, out)?;
+            }
+        }
+        Value::Object(fields) => write_map(fields, out, write_json)?,
+    }
+
+    Ok(())
+}
+
+fn write_map(
+    fields: &Map<String, Value>,
+    out: &mut Vec<u8>,
+    write_value: fn(&Value, &mut Vec<u8>) -> Result<(), SpudError>,
+) -> Result<(), SpudError> {
+    write_head(MAJOR_MAP, fields.len() as u64, out);
+
+    for (name, value) in fields {
+        write_text(name, out);
+        write_value(value, out)?;
+    }
+
+    Ok(())
+}
+
+fn write_text(text: &str, out: &mut Vec<u8>) {
+    write_head(MAJOR_TEXT, text.len() as u64, out);
+    out.extend_from_slice(text.as_bytes());
+}
+
+/// Writes integers as CBOR integers, falling back to bignums past 64 bits, and anything else as a double.
+fn write_number(number: &Number, out: &mut Vec<u8>) {
+    let text: String = number.to_string();
+
+    if let Ok(value) = text.parse::<u128>() {
+        write_unsigned(MAJOR_UNSIGNED, TAG_POSITIVE_BIGNUM, value, out);
+    } else if let Ok(value) = text.parse::<i128>() {
+        // CBOR stores a negative integer `n` as `-1 - n`, which always fits in a `u128`
+        write_unsigned(
+            MAJOR_NEGATIVE,
+            TAG_NEGATIVE_BIGNUM,
+            (-1 - value).cast_unsigned(),
+            out,
+        );
+    } else {
+        out.push(FLOAT64);
+        out.extend_from_slice(&number.as_f64().unwrap_or(f64::NAN).to_be_bytes());
+    }
+}
+
+fn write_unsigned(major: u8, bignum_tag: u64, value: u128, out: &mut Vec<u8>) {
+    if let Ok(value) = u64::try_from(value) {
+        write_head(major, value, out);
+
+        return;
+    }
+
+    let bytes: [u8; 16] = value.to_be_bytes();
+    let significant: &[u8] = &bytes[bytes.iter().take_while(|byte| **byte == 0).count()..];
+
+    write_head(MAJOR_TAG, bignum_tag, out);
+    write_head(MAJOR_BYTES, significant.len() as u64, out);
+    out.extend_from_slice(significant);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_head() {
+        let mut out: Vec<u8> = Vec::new();
+
+        write_head(MAJOR_UNSIGNED, 23, &mut out);
+        write_head(MAJOR_UNSIGNED, 24, &mut out);
+        write_head(MAJOR_TEXT, 0x1234, &mut out);
+        write_head(MAJOR_ARRAY, 0x1_0000, &mut out);
+        write_head(MAJOR_MAP, u64::MAX, &mut out);
+
+        assert_eq!(
+            out,
+            [
+                0x17, 0x18, 0x18, 0x79, 0x12, 0x34, 0x9A, 0x00, 0x01, 0x00, 0x00, 0xBB, 0xFF, 0xFF,
+                0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_number() {
+        let encode = |text: &str| {
+            let mut out: Vec<u8> = Vec::new();
+
+            write_number(&serde_json::from_str::<Number>(text).unwrap(), &mut out);
+
+            out
+        };
+
+        assert_eq!(encode("0"), [0x00]);
+        assert_eq!(encode("-1"), [0x20]);
+        assert_eq!(encode("-500"), [0x39, 0x01, 0xF3]);
+        assert_eq!(
+            encode("18446744073709551616"),
+            [0xC2, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            encode("-18446744073709551617"),
+            [0xC3, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(encode("1.5"), [0xFB, 0x3F, 0xF8, 0, 0, 0, 0, 0, 0]);
+    }
+}
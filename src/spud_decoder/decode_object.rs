@@ -1,11 +1,14 @@
 use indexmap::IndexMap;
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 use crate::{
-    SpudError,
-    spud_decoder::decoder_functions::{
-        array_start, binary_blob, bool as d_bool, date, date_time, decimal, null, number,
-        object_start, string, time,
+    OBJECT_FORMAT_VERSION, SpudError,
+    spud_decoder::{
+        DecoderOptions, NullRepr,
+        decoder_functions::{
+            array_start, binary_blob, bool as d_bool, char as d_char, date, date_time, decimal,
+            duration, enum_variant, ip_addr, null, number, object_start, string, time, uuid,
+        },
     },
     spud_types::{SpudNumberTypes, SpudTypes},
     types::{Date, Time},
@@ -17,12 +20,14 @@ pub(crate) struct DecoderObject<'a> {
     pub(crate) field_names: &'a IndexMap<u8, String>,
     pub(crate) current_byte: u8,
     pub(crate) current_field: String,
+    pub(crate) options: &'a DecoderOptions,
 }
 
 impl<'a> DecoderObject<'a> {
     pub(crate) fn new(
         contents: &'a [u8],
         field_names: &'a IndexMap<u8, String>,
+        options: &'a DecoderOptions,
     ) -> DecoderObject<'a> {
         DecoderObject {
             contents,
@@ -30,20 +35,27 @@ impl<'a> DecoderObject<'a> {
             field_names,
             current_byte: 0,
             current_field: String::new(),
+            options,
         }
     }
 
     pub(crate) fn decode(&mut self) -> Result<IndexMap<String, Value>, SpudError> {
         let mut object: IndexMap<String, Value> = IndexMap::new();
 
-        self.next(2)?;
+        let id: &[u8] = self.read_object_header()?;
 
-        let id: &[u8] = self.read_bytes(10)?;
+        if !self.options.omit_oid {
+            let object_id: String = bs58::encode(&id).into_string();
+            object.insert("oid".to_string(), Value::String(object_id));
+        }
 
-        let object_id: String = bs58::encode(&id).into_string();
-        object.insert("oid".to_string(), Value::String(object_id));
+        loop {
+            if self.index >= self.contents.len() {
+                return Err(SpudError::DecodingError(
+                    "unterminated object: the buffer ended before ObjectEnd".to_owned(),
+                ));
+            }
 
-        while self.index < self.contents.len() {
             if self.contents.get(self.index) == Some(&SpudTypes::ObjectEnd.as_u8())
                 && self.contents.get(self.index + 1) == Some(&SpudTypes::ObjectEnd.as_u8())
             {
@@ -52,30 +64,75 @@ impl<'a> DecoderObject<'a> {
 
             let field_value: Option<Value> = self.decode_byte(self.current_byte)?;
 
-            if let Some(value) = field_value {
-                object.insert(self.current_field.clone(), value);
+            if let Some(value) = field_value
+                && !self.is_omitted(&value)
+            {
+                object.insert(self.output_field_name(), value);
             }
         }
 
         Ok(object)
     }
 
-    /// # Panics
+    /// Reads a top-level object's `ObjectStart` pair, id and, in files declaring `Capabilities::OBJECT_VERSION`,
+    /// its format version, returning the id.
+    ///
+    /// Every version up to `OBJECT_FORMAT_VERSION` shares the field layout decoded here, newer versions are rejected.
+    pub(crate) fn read_object_header(&mut self) -> Result<&'a [u8], SpudError> {
+        self.next(2)?;
+
+        let id: &[u8] = self.read_bytes(10)?;
+
+        if self.options.object_versions {
+            let version: u8 = self.read_bytes(1)?[0];
+
+            if !(1..=OBJECT_FORMAT_VERSION).contains(&version) {
+                return Err(SpudError::DecodingError(format!(
+                    "unsupported object format version {version}, this decoder reads versions 1 to {OBJECT_FORMAT_VERSION}"
+                )));
+            }
+        }
+
+        Ok(id)
+    }
+
+    /// Returns `true` if `value` is a `Null` field that `NullRepr::Omit` leaves out of its object.
+    pub(crate) fn is_omitted(&self, value: &Value) -> bool {
+        self.options.null_as == NullRepr::Omit && value.is_null()
+    }
+
+    /// Returns the name the current field is written under, after applying `with_field_rename`.
+    pub(crate) fn output_field_name(&self) -> String {
+        self.options
+            .field_renames
+            .get(&self.current_field)
+            .unwrap_or(&self.current_field)
+            .clone()
+    }
+
+    /// Advances the cursor by `steps` bytes.
     ///
-    /// Will panic if the index is out of bounds
+    /// The cursor may land exactly on the end of the buffer, callers have to check
+    /// `index < contents.len()` before reading `current_byte` again.
     pub(crate) fn next(&mut self, steps: usize) -> Result<(), SpudError> {
-        if self.index + steps >= self.contents.len() {
+        if self
+            .index
+            .checked_add(steps)
+            .is_none_or(|end| end > self.contents.len())
+        {
             return Err(SpudError::DecodingError(format!(
                 "Index out of bounds, current index: {}, object length: {}, tried to read: {}",
                 self.index,
                 self.contents.len(),
-                self.index + steps
+                self.index.saturating_add(steps)
             )));
         }
 
         self.index += steps;
 
-        self.current_byte = self.contents[self.index];
+        if let Some(&byte) = self.contents.get(self.index) {
+            self.current_byte = byte;
+        }
 
         Ok(())
     }
@@ -98,9 +155,11 @@ impl<'a> DecoderObject<'a> {
         Ok(1)
     }
 
-    /// # Panics
+    /// Reads the length prefix of a string or blob, leaving the cursor on the first byte of the data.
+    ///
+    /// # Errors
     ///
-    /// Will panic on unknown token
+    /// Returns an error if the prefix is not tagged as a U8, U16, U32 or U64, or if it runs past the buffer.
     pub(crate) fn read_variable_length_data(&mut self) -> Result<usize, SpudError> {
         self.next(1)?;
 
@@ -109,9 +168,14 @@ impl<'a> DecoderObject<'a> {
             val if val == SpudTypes::Number(SpudNumberTypes::U16).as_u8() => 2,
             val if val == SpudTypes::Number(SpudNumberTypes::U32).as_u8() => 4,
             val if val == SpudTypes::Number(SpudNumberTypes::U64).as_u8() => 8,
-            _ => Err(SpudError::DecodingError(
-                "Expected: U8, U16, U32, U64, but got an unknown token".to_string(),
-            ))?,
+            tag => Err(SpudError::DecodingError(format!(
+                "Expected a U8, U16, U32 or U64 length prefix at index {}, found {}",
+                self.index,
+                SpudTypes::from_u8(tag).map_or_else(
+                    || format!("{tag:#04X}"),
+                    |spud_type| spud_type.name().to_owned()
+                )
+            )))?,
         };
 
         self.next(1)?;
@@ -134,23 +198,37 @@ impl<'a> DecoderObject<'a> {
                     .try_into()
                     .map_err(|_| SpudError::DecodingError("Invalid U32 bytes".to_owned()))?,
             ) as usize,
-            8 => {
-                usize::try_from(u64::from_le_bytes(read_bytes.try_into().map_err(|_| {
-                    SpudError::DecodingError("Invalid U64 bytes".to_owned())
-                })?))?
-            }
+            8 => addressable_length(u64::from_le_bytes(
+                read_bytes
+                    .try_into()
+                    .map_err(|_| SpudError::DecodingError("Invalid U64 bytes".to_owned()))?,
+            ))?,
             _ => unreachable!(),
         })
     }
 
     pub(crate) fn read_bytes(&mut self, steps: usize) -> Result<&'a [u8], SpudError> {
-        let result: &[u8] = &self.contents[self.index..self.index + steps];
+        let result: &[u8] = self.peek_bytes(steps)?;
 
         self.next(steps)?;
 
         Ok(result)
     }
 
+    /// Returns the next `steps` bytes without advancing the cursor.
+    pub(crate) fn peek_bytes(&self, steps: usize) -> Result<&'a [u8], SpudError> {
+        self.contents
+            .get(self.index..self.index.saturating_add(steps))
+            .ok_or_else(|| {
+                SpudError::DecodingError(format!(
+                    "Index out of bounds, current index: {}, object length: {}, tried to read: {}",
+                    self.index,
+                    self.contents.len(),
+                    self.index.saturating_add(steps)
+                ))
+            })
+    }
+
     pub(crate) fn read_date(read_bytes: &[u8]) -> Result<Date, SpudError> {
         let year: u16 = u16::from_le_bytes(
             read_bytes[0..2]
@@ -193,14 +271,19 @@ impl<'a> DecoderObject<'a> {
             Ok(None)
         } else {
             let return_value: Value = match decode_result {
-                Some(SpudTypes::Null) => null(&mut next_steps),
+                Some(SpudTypes::Null) => null(self, &mut next_steps),
                 Some(SpudTypes::Bool) => d_bool(self, &mut next_steps)?,
                 Some(SpudTypes::Number(number_type)) => number(self, number_type)?,
                 Some(SpudTypes::Decimal) => decimal(self)?,
+                Some(SpudTypes::Char) => d_char(self)?,
                 Some(SpudTypes::String) => string(self, &mut next_steps)?,
                 Some(SpudTypes::Date) => date(self)?,
                 Some(SpudTypes::Time) => time(self)?,
                 Some(SpudTypes::DateTime) => date_time(self)?,
+                Some(SpudTypes::Duration) => duration(self)?,
+                Some(SpudTypes::Enum) => enum_variant(self, &mut next_steps)?,
+                Some(SpudTypes::Uuid) => uuid(self)?,
+                Some(SpudTypes::IpAddr) => ip_addr(self)?,
                 Some(SpudTypes::BinaryBlob) => binary_blob(self, &mut next_steps)?,
                 Some(SpudTypes::ArrayStart) => array_start(self, &mut next_steps)?,
                 Some(SpudTypes::ObjectStart) => object_start(self, &mut next_steps)?,
@@ -212,7 +295,93 @@ impl<'a> DecoderObject<'a> {
 
             self.next(next_steps)?;
 
-            Ok(Some(return_value))
+            match decode_result {
+                Some(spud_type) if self.options.annotate_types => {
+                    let mut annotated: Map<String, Value> = Map::new();
+
+                    annotated.insert(
+                        "type".to_owned(),
+                        Value::String(spud_type.name().to_owned()),
+                    );
+                    annotated.insert("value".to_owned(), return_value);
+
+                    Ok(Some(Value::Object(annotated)))
+                }
+                _ => Ok(Some(return_value)),
+            }
         }
     }
 }
+
+/// Converts a decoded `u64` length prefix to `usize`, which only fails on targets narrower than 64 bits.
+fn addressable_length(length: u64) -> Result<usize, SpudError> {
+    usize::try_from(length).map_err(|_| {
+        SpudError::DecodingError("length exceeds platform addressable size".to_owned())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_ending_at_buffer_end() {
+        let field_names: IndexMap<u8, String> = IndexMap::new();
+        let options: DecoderOptions = DecoderOptions::default();
+
+        let mut contents: Vec<u8> = vec![SpudTypes::Number(SpudNumberTypes::U32).as_u8()];
+
+        contents.extend_from_slice(&70_000u32.to_le_bytes());
+
+        let mut decoder: DecoderObject<'_> = DecoderObject::new(&contents, &field_names, &options);
+
+        assert_eq!(
+            decoder.decode_byte(contents[0]).unwrap(),
+            Some(Value::from(70_000))
+        );
+        assert_eq!(decoder.index, contents.len());
+
+        let truncated: &[u8] = &contents[..contents.len() - 1];
+
+        let mut decoder: DecoderObject<'_> = DecoderObject::new(truncated, &field_names, &options);
+
+        assert!(decoder.decode_byte(truncated[0]).is_err());
+    }
+
+    #[test]
+    fn test_length_prefix_names_unexpected_tag() {
+        let field_names: IndexMap<u8, String> = IndexMap::new();
+        let options: DecoderOptions = DecoderOptions::default();
+
+        let mut contents: Vec<u8> = vec![
+            SpudTypes::String.as_u8(),
+            SpudTypes::Number(SpudNumberTypes::I32).as_u8(),
+        ];
+
+        contents.extend_from_slice(&5i32.to_le_bytes());
+
+        let mut decoder: DecoderObject<'_> = DecoderObject::new(&contents, &field_names, &options);
+
+        let Err(SpudError::DecodingError(message)) = decoder.read_variable_length_data() else {
+            panic!("expected a decoding error");
+        };
+
+        assert_eq!(
+            message,
+            "Expected a U8, U16, U32 or U64 length prefix at index 1, found I32"
+        );
+
+        contents[1] = 0xFF;
+
+        let mut decoder: DecoderObject<'_> = DecoderObject::new(&contents, &field_names, &options);
+
+        let Err(SpudError::DecodingError(message)) = decoder.read_variable_length_data() else {
+            panic!("expected a decoding error");
+        };
+
+        assert_eq!(
+            message,
+            "Expected a U8, U16, U32 or U64 length prefix at index 1, found 0xFF"
+        );
+    }
+}
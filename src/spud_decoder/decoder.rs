@@ -1,7 +1,10 @@
-use std::path::Path;
+use std::{collections::HashMap, mem, ops::Range, path::Path};
 
 use indexmap::IndexMap;
-use serde_json::Value;
+use serde_json::{Map, Value};
+
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
 
 #[cfg(feature = "async")]
 use tokio::{
@@ -12,41 +15,143 @@ use tokio::{
 #[cfg(feature = "sync")]
 use std::{
     fs::{File as StdFile, read as std_read},
-    io::Write,
+    io::{BufReader, Read, Seek, SeekFrom, Write},
+};
+
+#[cfg(feature = "sync")]
+use crate::spud_decoder::HeaderInfo;
+
+#[cfg(feature = "cbor")]
+use crate::spud_decoder::cbor;
+
+use crate::{
+    SPUD_VERSION, SpudError, SpudSchema,
+    functions::{crc32, sha256},
+    spud_decoder::{
+        BlobEncoding, DecoderObject, DecoderOptions, NullRepr, SpudObjectIter,
+        decoder_functions::ip_addr_width,
+    },
+    spud_types::SpudTypes,
+    types::{Capabilities, ObjectId},
 };
 
-use crate::{SPUD_VERSION, SpudError, spud_decoder::DecoderObject, spud_types::SpudTypes};
+/// How deeply objects may nest before the object scan gives up on a file.
+const MAX_SCAN_DEPTH: usize = 1024;
+
+/// The size of the CRC-32 following every top-level object in files declaring `Capabilities::CHECKSUM`.
+const CHECKSUM_LEN: usize = 4;
+
+/// The size of the field-name table offset written before the trailer of files declaring `Capabilities::FOOTER_FIELD_TABLE`.
+const FOOTER_OFFSET_LEN: usize = 8;
 
 /// The `SpudDecoder` is responsible for decoding SPUD files into a JSON format.
 #[derive(Default, Debug, Clone)]
 pub struct SpudDecoder {
-    file_contents: Vec<u8>,
-    field_names: IndexMap<u8, String>,
+    pub(crate) file_contents: Vec<u8>,
+    pub(crate) field_names: IndexMap<u8, String>,
+    body_offset: usize,
+    capabilities: Capabilities,
     output_json: String,
+    options: DecoderOptions,
 }
 
 impl SpudDecoder {
     /// # Errors
     ///
-    /// Returns an error if the file is not a valid spud file
+    /// Returns an error if the file is not a valid spud file, or if its trailer declares a reserved capability
     ///
     /// # Panics
     ///
     /// Panics if the SPUD version environment variable is not set or if the file is invalid.
     pub fn new(file: &[u8]) -> Result<Self, SpudError> {
-        let spud_version_bytes: Vec<u8> = SPUD_VERSION.as_bytes().to_vec();
-        let spud_version_len: usize = spud_version_bytes.len();
+        let (field_names, body_start): (IndexMap<u8, String>, usize) = Self::parse_header(file)?;
+
+        Self::from_parts(file[body_start..].to_vec(), field_names, body_start)
+    }
+
+    /// Creates a new `SpudDecoder` that takes ownership of the file bytes.
+    ///
+    /// Unlike `new`, the file body is not copied: the header is removed from the buffer in place,
+    /// so decoding a file read into memory does not need room for a second copy of it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is not a valid spud file
+    pub fn from_vec(mut file: Vec<u8>) -> Result<Self, SpudError> {
+        let (field_names, body_start): (IndexMap<u8, String>, usize) = Self::parse_header(&file)?;
+
+        file.drain(..body_start);
+
+        Self::from_parts(file, field_names, body_start)
+    }
+
+    /// Splits the trailer, and the field-name table of `Capabilities::FOOTER_FIELD_TABLE` files, off the file body,
+    /// so only object bytes are left to scan.
+    fn from_parts(
+        mut file_contents: Vec<u8>,
+        field_names: IndexMap<u8, String>,
+        body_offset: usize,
+    ) -> Result<Self, SpudError> {
+        let Some(trailer_start) = file_contents.len().checked_sub(4) else {
+            return Err(SpudError::InvalidSpudFile(
+                "missing trailer, the file may be truncated".to_owned(),
+            ));
+        };
+
+        let mut trailer: [u8; 4] = [0; 4];
+
+        trailer.copy_from_slice(&file_contents[trailer_start..]);
+        file_contents.truncate(trailer_start);
+
+        let capabilities: Capabilities = Capabilities::from_trailer(trailer)?;
+
+        let reserved: Capabilities = capabilities.reserved();
+
+        if !reserved.is_empty() {
+            return Err(SpudError::InvalidSpudFile(format!(
+                "the trailer declares the reserved capabilities {reserved:?}, which this version cannot decode"
+            )));
+        }
+
+        let field_names: IndexMap<u8, String> =
+            if capabilities.contains(Capabilities::FOOTER_FIELD_TABLE) {
+                Self::split_field_footer(&mut file_contents, body_offset)?
+            } else {
+                field_names
+            };
 
-        let (file_version, file_contents): (&[u8], &[u8]) = file.split_at(spud_version_len);
+        Ok(Self {
+            file_contents,
+            field_names,
+            body_offset,
+            capabilities,
+            output_json: String::new(),
+            options: DecoderOptions {
+                object_versions: capabilities.contains(Capabilities::OBJECT_VERSION),
+                ..DecoderOptions::default()
+            },
+        })
+    }
+
+    /// Reads the version and the field name list, returning the field names and the offset of the first object byte.
+    fn parse_header(file: &[u8]) -> Result<(IndexMap<u8, String>, usize), SpudError> {
+        let spud_version_bytes: &[u8] = SPUD_VERSION.as_bytes();
+        let spud_version_len: usize = spud_version_bytes.len();
 
-        if file_version != spud_version_bytes {
+        if !file.starts_with(spud_version_bytes) {
             return Err(SpudError::DecodingError(
                 "Invalid SPUD file: version mismatch".to_owned(),
             ));
         }
 
-        let mut file_contents: Vec<u8> = file_contents.to_vec();
+        let (field_names, table_len): (IndexMap<u8, String>, usize) =
+            Self::parse_field_table(&file[spud_version_len..])?;
 
+        Ok((field_names, spud_version_len + table_len))
+    }
+
+    /// Reads a field name list, returning the field names and the length of the list, `FieldNameListEnd` included.
+    fn parse_field_table(file_contents: &[u8]) -> Result<(IndexMap<u8, String>, usize), SpudError> {
         let mut field_names: IndexMap<u8, String> = IndexMap::new();
 
         let field_name_list_end_byte_index: Option<usize> = file_contents
@@ -55,12 +160,11 @@ impl SpudDecoder {
 
         match field_name_list_end_byte_index {
             Some(index) => {
-                let (field_names_bytes, file_content): (&[u8], &[u8]) =
-                    file_contents.split_at(index + 1);
+                let field_names_bytes: &[u8] = &file_contents[..=index];
 
                 let mut cursor: usize = 0;
 
-                loop {
+                while field_names_bytes[cursor] != SpudTypes::FieldNameListEnd.as_u8() {
                     let field_name_length: u8 = field_names_bytes[cursor];
 
                     cursor += 1;
@@ -80,27 +184,269 @@ impl SpudDecoder {
                     let decoded_field_name: String = String::from_utf8(field_name)?;
 
                     field_names.insert(field_id, decoded_field_name);
-
-                    if field_names_bytes[cursor] == SpudTypes::FieldNameListEnd.as_u8() {
-                        break;
-                    }
                 }
 
-                file_contents = file_content.to_vec();
+                Ok((field_names, index + 1))
             }
             None => Err(SpudError::DecodingError(
                 "Invalid SPUD file: missing field name list end byte".to_owned(),
-            ))?,
+            )),
         }
+    }
 
-        Ok(Self {
-            file_contents,
-            field_names,
-            output_json: String::new(),
-        })
+    /// Reads the field-name table at the end of a `Capabilities::FOOTER_FIELD_TABLE` file body and removes it,
+    /// along with the `u64` file offset of the table that follows it.
+    fn split_field_footer(
+        file_contents: &mut Vec<u8>,
+        body_offset: usize,
+    ) -> Result<IndexMap<u8, String>, SpudError> {
+        let invalid = || {
+            SpudError::InvalidSpudFile(
+                "invalid footer field table offset, the file may be truncated".to_owned(),
+            )
+        };
+
+        let offset_start: usize = file_contents
+            .len()
+            .checked_sub(FOOTER_OFFSET_LEN)
+            .ok_or_else(invalid)?;
+
+        let mut offset: [u8; FOOTER_OFFSET_LEN] = [0; FOOTER_OFFSET_LEN];
+
+        offset.copy_from_slice(&file_contents[offset_start..]);
+
+        let table_start: usize = usize::try_from(u64::from_le_bytes(offset))
+            .ok()
+            .and_then(|offset| offset.checked_sub(body_offset))
+            .filter(|&table_start| table_start < offset_start)
+            .ok_or_else(invalid)?;
+
+        let (field_names, table_len): (IndexMap<u8, String>, usize) =
+            Self::parse_field_table(&file_contents[table_start..offset_start])?;
+
+        if table_start + table_len != offset_start {
+            return Err(invalid());
+        }
+
+        file_contents.truncate(table_start);
+
+        Ok(field_names)
+    }
+
+    /// Controls whether whole-valued floats are rendered as integers, so `42.0` decodes to `42`.
+    ///
+    /// # Arguments
+    ///
+    /// * `normalize` - Whether to render whole-valued `f32`/`f64` values as integers, defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "sync")] {
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("value", 42.0f64)?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+    ///
+    /// let mut decoder = SpudDecoder::new(&encoded_bytes).unwrap();
+    ///
+    /// decoder.with_normalize_number_display(true);
+    ///
+    /// assert!(decoder.decode(false, false).unwrap().contains(r#""value":42}"#));
+    /// # }
+    /// ```
+    pub fn with_normalize_number_display(&mut self, normalize: bool) -> &mut Self {
+        self.options.normalize_number_display = normalize;
+
+        self
+    }
+
+    /// Controls whether decimals are decoded as `{"unscaled": "...", "scale": n}` objects instead of strings.
+    ///
+    /// The unscaled value is a string, since it can exceed the range of JSON numbers, e.g. `1.50` becomes
+    /// `{"unscaled": "150", "scale": 2}`.
+    ///
+    /// # Arguments
+    ///
+    /// * `as_parts` - Whether to split decimals into their unscaled value and scale, defaults to `false`.
+    pub fn with_decimal_as_parts(&mut self, as_parts: bool) -> &mut Self {
+        self.options.decimal_as_parts = as_parts;
+
+        self
+    }
+
+    /// Controls whether decimals are decoded as JSON numbers rather than strings, when no precision is lost.
+    ///
+    /// A decimal without a fractional part becomes an integer if it fits 64 bits, any other decimal becomes
+    /// a float if that float reads back as the same value. Decimals that cannot be represented exactly,
+    /// e.g. `12345678901234567890.123456789`, are still decoded as strings. `with_decimal_as_parts` takes
+    /// precedence over this flag.
+    ///
+    /// # Arguments
+    ///
+    /// * `as_number` - Whether to decode representable decimals as numbers, defaults to `false`.
+    pub fn with_decimal_as_number(&mut self, as_number: bool) -> &mut Self {
+        self.options.decimal_as_number = as_number;
+
+        self
+    }
+
+    /// Controls whether every decoded value is wrapped with the SPUD type it was encoded as.
+    ///
+    /// Meant for debugging, e.g. a date field decodes to `{"type": "Date", "value": "2023-03-15"}`
+    /// and a `u8` to `{"type": "U8", "value": 42}`. Array elements and nested fields are annotated too.
+    ///
+    /// # Arguments
+    ///
+    /// * `annotate` - Whether to annotate values with their type, defaults to `false`.
+    pub fn with_annotate_types(&mut self, annotate: bool) -> &mut Self {
+        self.options.annotate_types = annotate;
+
+        self
+    }
+
+    /// Controls how binary blobs are rendered, as an array of bytes, a base64 string or a hex string.
+    ///
+    /// # Arguments
+    ///
+    /// * `encoding` - The rendering to use for blobs, defaults to `BlobEncoding::Array`.
+    pub fn with_blob_encoding(&mut self, encoding: BlobEncoding) -> &mut Self {
+        self.options.blob_encoding = encoding;
+
+        self
+    }
+
+    /// Controls whether arrays made only of `u8` values are rendered as blobs.
+    ///
+    /// `add_value(&[u8])` writes a number array rather than a blob, so data stored that way decodes to an
+    /// array of numbers. With this flag such arrays are rendered per the configured `BlobEncoding` instead.
+    /// This is a heuristic: an intentional, non-empty array of `u8` numbers is coalesced as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `coalesce` - Whether to render `u8`-only arrays as blobs, defaults to `false`.
+    pub fn with_coalesce_byte_arrays(&mut self, coalesce: bool) -> &mut Self {
+        self.options.coalesce_byte_arrays = coalesce;
+
+        self
+    }
+
+    /// Controls how `Null` values are rendered: as JSON `null`, as a sentinel string, or left out of their object.
+    ///
+    /// # Arguments
+    ///
+    /// * `null_as` - The rendering to use for `Null` values, defaults to `NullRepr::JsonNull`.
+    pub fn with_null_as(&mut self, null_as: NullRepr) -> &mut Self {
+        self.options.null_as = null_as;
+
+        self
+    }
+
+    /// Controls whether `f32` values are rendered with the shortest decimal that reads back as the same `f32`.
+    ///
+    /// By default an `f32` is widened to `f64` before rendering, which exposes its binary representation,
+    /// e.g. `3.15f32` decodes to `3.1500000953674316`. With this flag it decodes to `3.15`.
+    ///
+    /// # Arguments
+    ///
+    /// * `shortest` - Whether to render `f32` values with their shortest round-tripping decimal, defaults to `false`.
+    pub fn with_shortest_f32(&mut self, shortest: bool) -> &mut Self {
+        self.options.shortest_f32 = shortest;
+
+        self
+    }
+
+    /// Controls whether every decoded object, nested ones included, carries its id as an `oid` field.
+    ///
+    /// Leaving the ids out makes the output comparable with JSON that never had them, e.g. the document
+    /// the file was built from.
+    ///
+    /// # Arguments
+    ///
+    /// * `include` - Whether to add the `oid` field to objects, defaults to `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "sync")] {
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("value", 1u8)?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+    ///
+    /// let mut decoder = SpudDecoder::new(&encoded_bytes).unwrap();
+    ///
+    /// decoder.with_include_oid(false);
+    ///
+    /// assert_eq!(decoder.decode(false, false).unwrap(), r#"{"value":1}"#);
+    /// # }
+    /// ```
+    pub fn with_include_oid(&mut self, include: bool) -> &mut Self {
+        self.options.omit_oid = !include;
+
+        self
+    }
+
+    /// Renames fields in the decoded output, e.g. to match a JSON schema whose names differ from the file's.
+    ///
+    /// Every field, nested ones included, whose name in the file is a key of `map` is written under the
+    /// mapped name instead, fields that are not in `map` keep their name. The file's field-name table,
+    /// error messages and schema validation still use the original names. Replaces any previous map.
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - The output name of each renamed field, keyed by its name in the file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "sync")] {
+    /// use std::collections::HashMap;
+    ///
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("usr_nm", "Alice")?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+    ///
+    /// let mut decoder = SpudDecoder::new(&encoded_bytes).unwrap();
+    ///
+    /// decoder.with_field_rename(HashMap::from([("usr_nm".to_owned(), "userName".to_owned())]));
+    ///
+    /// assert_eq!(decoder.decode_values().unwrap()[0]["userName"], "Alice");
+    /// # }
+    /// ```
+    pub fn with_field_rename(&mut self, map: HashMap<String, String>) -> &mut Self {
+        self.options.field_renames = map;
+
+        self
     }
 
     /// Decodes the SPUD file contents into a JSON string.
+    ///
+    /// A file without any object, such as the output of a builder that never called `object()`,
+    /// is valid and decodes to an empty array `[]` whatever `want_array` is.
+    ///
     /// # Arguments
     ///
     /// * `pretty` - Whether to format the JSON output with indentation.
@@ -109,157 +455,1199 @@ impl SpudDecoder {
     ///
     /// Returns an error if serde fails to serialize the file
     pub fn decode(&mut self, pretty: bool, want_array: bool) -> Result<&str, SpudError> {
-        let objects: Vec<IndexMap<String, Value>> = self.decode_objects()?;
+        let mut output_json: String = mem::take(&mut self.output_json);
 
-        let output_json: Result<String, serde_json::Error> = if objects.len() == 1 && !want_array {
-            let single_object: &IndexMap<String, Value> = &objects[0];
-
-            if pretty {
-                serde_json::to_string_pretty(single_object)
-            } else {
-                serde_json::to_string(single_object)
-            }
-        } else if pretty {
-            serde_json::to_string_pretty(&objects)
-        } else {
-            serde_json::to_string(&objects)
-        };
+        self.decode_into_string(&mut output_json, pretty, want_array)?;
 
-        match output_json {
-            Ok(json) => {
-                self.output_json = json;
-            }
-            Err(err) => {
-                Err(SpudError::DecodingError(format!(
-                    "Failed to serialize JSON: {err}"
-                )))?;
-            }
-        }
+        self.output_json = output_json;
 
         Ok(self.output_json.as_str())
     }
 
-    fn decode_objects(&mut self) -> Result<Vec<IndexMap<String, Value>>, SpudError> {
-        let mut decoded_objects: Vec<IndexMap<String, Value>> = Vec::new();
-        let mut i: usize = 0;
+    /// Decodes the SPUD file contents into a caller-owned `String`, replacing its contents.
+    ///
+    /// The output is the same as `decode`, but the string's capacity is reused, so decoding many
+    /// similar files into the same buffer stops allocating once it is large enough.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The string the JSON is written to, cleared first and left empty on error.
+    /// * `pretty` - Whether to format the JSON output with indentation.
+    /// * `want_array` - Whether to wrap the output in an array, useless if the decoder finds more than one object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be decoded or if serde fails to serialize it
+    pub fn decode_into_string(
+        &self,
+        out: &mut String,
+        pretty: bool,
+        want_array: bool,
+    ) -> Result<(), SpudError> {
+        let value: Value = self.decode_to_value(want_array)?;
 
-        while i < self.file_contents.len() {
-            if self.file_contents.get(i) == Some(&SpudTypes::ObjectStart.as_u8())
-                && self.file_contents.get(i + 1) == Some(&SpudTypes::ObjectStart.as_u8())
-            {
-                let start: usize = i;
-
-                let mut depth: i32 = 0;
-                let mut end: usize = 0;
-                let mut j: usize = i;
-
-                while let Some(&byte) = self.file_contents.get(j) {
-                    if byte == SpudTypes::ObjectStart.as_u8()
-                        && self.file_contents.get(j + 1) == Some(&SpudTypes::ObjectStart.as_u8())
-                    {
-                        depth += 1;
-                        j += 1;
-                    } else if byte == SpudTypes::ObjectEnd.as_u8()
-                        && self.file_contents.get(j + 1) == Some(&SpudTypes::ObjectEnd.as_u8())
-                    {
-                        depth -= 1;
-                        j += 1;
-
-                        if depth == 0 {
-                            end = j + 1;
-
-                            break;
-                        }
-                    }
+        let mut bytes: Vec<u8> = mem::take(out).into_bytes();
 
-                    j += 1;
-                }
+        bytes.clear();
+
+        let written: Result<(), serde_json::Error> = if pretty {
+            serde_json::to_writer_pretty(&mut bytes, &value)
+        } else {
+            serde_json::to_writer(&mut bytes, &value)
+        };
 
-                if end > start {
-                    let object_bytes: &[u8] = &self.file_contents[start..end];
+        if let Err(err) = written {
+            return Err(SpudError::DecodingError(format!(
+                "Failed to serialize JSON: {err}"
+            )));
+        }
 
-                    let mut decoder: DecoderObject<'_> =
-                        DecoderObject::new(object_bytes, &self.field_names);
+        *out = String::from_utf8(bytes)?;
 
-                    decoded_objects.push(decoder.decode()?);
+        Ok(())
+    }
 
-                    i = end;
-                } else {
-                    i += 1;
-                }
-            } else {
-                i += 1;
-            }
+    /// Decodes the SPUD file contents into a `serde_json::Value`, without going through a JSON string.
+    ///
+    /// The value has the same shape as the output of `decode`, an empty array for a file without objects,
+    /// and the JSON kept for `build_file` is left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `want_array` - Whether to wrap the output in an array, useless if the decoder finds more than one object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be decoded
+    pub fn decode_to_value(&self, want_array: bool) -> Result<Value, SpudError> {
+        let mut objects: Vec<Value> = self.decode_values()?;
+
+        if objects.len() == 1 && !want_array {
+            return Ok(objects.remove(0));
         }
 
-        Ok(decoded_objects)
+        Ok(Value::Array(objects))
     }
-}
 
-#[cfg(feature = "sync")]
-impl SpudDecoder {
-    /// Creates a new `SpudDecoder` instance from a file at the specified path.
+    /// Decodes a file expected to hold exactly one top-level object, e.g. a config or a single message.
     ///
-    /// # Arguments
+    /// # Examples
     ///
-    /// * `path` - The path to the file to read.
+    /// ```rust
+    /// # #[cfg(feature = "sync")] {
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder};
     ///
-    /// # Panics
+    /// let builder = SpudBuilderSync::new();
     ///
-    /// Will panic if the path is invalid
+    /// builder.object(|obj| {
+    ///     obj.add_value("value", 42u8)?;
     ///
-    /// # Errors
+    ///     Ok(())
+    /// }).unwrap();
     ///
-    /// Will return an error if the path is invalid
+    /// let encoded_bytes: Vec<u8> = builder.encode().unwrap();
     ///
-    /// # Notes
+    /// let object = SpudDecoder::new(&encoded_bytes).unwrap().decode_single().unwrap();
     ///
-    /// There is an async version of this function available if the `async` feature is enabled.
-    pub fn new_from_path(path: &str) -> Result<Self, SpudError> {
-        let file: Vec<u8> = std_read(path)?;
+    /// assert_eq!(object["value"], 42);
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SpudError::DecodingError` if the file holds no object or more than one,
+    /// or an error if the file cannot be decoded
+    pub fn decode_single(&self) -> Result<IndexMap<String, Value>, SpudError> {
+        let mut objects: Vec<IndexMap<String, Value>> = self.decode_objects()?;
+
+        if objects.len() != 1 {
+            return Err(SpudError::DecodingError(format!(
+                "Expected exactly one object, found {}",
+                objects.len()
+            )));
+        }
 
-        Self::new(&file)
+        Ok(objects.remove(0))
     }
 
-    /// Builds a JSON file at the specified path with the given file name.
-    ///  # Arguments
+    /// Decodes the SPUD file contents into a JSON string, after checking every top-level object against a schema.
     ///
-    /// * `path_str` - The path to the directory where the file will be created.
-    /// * `file_name` - The name of the file to create.
+    /// Each object must have all the schema's required fields with values of the declared types,
+    /// optional fields may be missing. Arrays and nested objects are checked element by element. The output is the same as `decode(false, false)`.
     ///
-    /// # Panics
+    /// # Arguments
     ///
-    /// Panics if the file has errors being written
+    /// * `schema` - The schema every object has to conform to.
     ///
     /// # Errors
     ///
-    /// Will return an error if the file has errors being written
+    /// Returns a `SpudError::ValidationError` naming the object and field of the first violation,
+    /// or an error if the file cannot be decoded
+    pub fn decode_validated(&mut self, schema: &SpudSchema) -> Result<&str, SpudError> {
+        let options: DecoderOptions = DecoderOptions {
+            annotate_types: true,
+            object_versions: self.options.object_versions,
+            ..DecoderOptions::default()
+        };
+
+        for (index, range) in self.object_ranges()?.into_iter().enumerate() {
+            let mut object: DecoderObject<'_> =
+                DecoderObject::new(&self.file_contents[range], &self.field_names, &options);
+
+            schema
+                .validate(&object.decode()?)
+                .map_err(|err| match err {
+                    SpudError::ValidationError(message) => {
+                        SpudError::ValidationError(format!("object {index}: {message}"))
+                    }
+                    err => err,
+                })?;
+        }
+
+        self.decode(false, false)
+    }
+
+    /// Decodes the SPUD file contents into JSON Lines, emitting one line per object.
     ///
-    /// # Notes
+    /// Nested objects are emitted as their own lines instead of being inlined in their parent,
+    /// and every line carries a `"_path"` field with the object's location in the document,
+    /// e.g. `[0]` for the first top-level object, `[0].userdata` for an object nested under it,
+    /// and `[0].items[1]` for an object stored inside an array. In its parent, such an array keeps a
+    /// `{"_ref": "[0].items[1]"}` marker in place of the object, so the other elements keep their index.
     ///
-    /// There is an async version of this function available if the `async` feature is enabled.
-    pub fn build_file(&self, path: &str) -> Result<(), SpudError> {
-        StdFile::create(Path::new(path))?.write_all(self.output_json.as_bytes())?;
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be decoded or if serde fails to serialize a line
+    pub fn decode_objects_flattened_ndjson(&mut self) -> Result<String, SpudError> {
+        let objects: Vec<IndexMap<String, Value>> = self.decode_objects()?;
 
-        Ok(())
+        let mut lines: Vec<Map<String, Value>> = Vec::new();
+
+        for (index, object) in objects.into_iter().enumerate() {
+            flatten_object(&format!("[{index}]"), object, &mut lines);
+        }
+
+        let mut output: String = String::new();
+
+        for line in &lines {
+            output.push_str(&serde_json::to_string(line)?);
+            output.push('\n');
+        }
+
+        Ok(output)
     }
-}
 
-#[cfg(feature = "async")]
-impl SpudDecoder {
-    /// Creates a new `SpudDecoder` instance from a file at the specified path.
+    /// Decodes every top-level object into a flat map, e.g. for loading into a columnar store.
+    ///
+    /// Nested objects are inlined in their parent, their fields keyed by the path from the top-level object
+    /// joined with `separator`, and array elements are keyed by their index, so `{"a": {"b": 1}, "c": [2]}`
+    /// becomes `{"a.b": 1, "c[0]": 2}` with a `.` separator. Empty objects and arrays are kept as values,
+    /// and nested objects contribute their `oid` like any other field unless `with_include_oid(false)` is set.
     ///
     /// # Arguments
     ///
-    /// * `path` - The path to the file to read.
+    /// * `separator` - The string placed between a field name and the name of a field nested under it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "sync")] {
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.object("user", |inner| {
+    ///         inner.add_value("age", 30u8)?;
+    ///
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+    ///
+    /// let mut decoder = SpudDecoder::new(&encoded_bytes).unwrap();
+    ///
+    /// decoder.with_include_oid(false);
+    ///
+    /// assert_eq!(decoder.decode_flat(".").unwrap()[0]["user.age"], 30);
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be decoded
+    pub fn decode_flat(&self, separator: &str) -> Result<Vec<IndexMap<String, Value>>, SpudError> {
+        Ok(self
+            .decode_objects()?
+            .into_iter()
+            .map(|object| {
+                let mut flat: IndexMap<String, Value> = IndexMap::new();
+
+                for (field, value) in object {
+                    flatten_keys(field, value, separator, &mut flat);
+                }
+
+                flat
+            })
+            .collect())
+    }
+
+    /// Returns the ids of the top-level objects in the file, without decoding their values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an object is too short to hold an id
+    pub fn object_ids(&self) -> Result<Vec<ObjectId>, SpudError> {
+        self.object_ranges()?
+            .into_iter()
+            .map(|range| {
+                let id_bytes: [u8; 10] = self
+                    .file_contents
+                    .get(range.start + 2..range.start + 12)
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or_else(|| {
+                        SpudError::DecodingError(format!(
+                            "Object at byte {} is too short to hold an id",
+                            range.start
+                        ))
+                    })?;
+
+                Ok(ObjectId::from(id_bytes))
+            })
+            .collect()
+    }
+
+    /// Returns the number of top-level objects in the file.
+    ///
+    /// Object boundaries are found by stepping over each value's type tag and width, no value is decoded
+    /// and nothing is allocated per object. Pair it with `objects` to decode them one at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "sync")] {
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// for i in 0..3u8 {
+    ///     builder.object(|obj| {
+    ///         obj.add_value("index", i)?;
+    ///
+    ///         Ok(())
+    ///     }).unwrap();
+    /// }
+    ///
+    /// let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+    ///
+    /// assert_eq!(SpudDecoder::new(&encoded_bytes).unwrap().object_count().unwrap(), 3);
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an object is malformed or unterminated
+    pub fn object_count(&self) -> Result<usize, SpudError> {
+        let mut count: usize = 0;
+        let mut i: usize = 0;
+
+        while let Some(range) = self.next_object_range(i)? {
+            i = range.end + self.checksum_len();
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Returns the oldest and newest creation times of the top-level objects, in seconds since the UNIX epoch.
+    ///
+    /// The times are read from the object ids, so no value is decoded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file has no objects or if an object id cannot be read
+    pub fn time_range(&self) -> Result<(u32, u32), SpudError> {
+        let timestamps: Vec<u32> = self.object_ids()?.iter().map(ObjectId::timestamp).collect();
+
+        match (timestamps.iter().min(), timestamps.iter().max()) {
+            (Some(min), Some(max)) => Ok((*min, *max)),
+            _ => Err(SpudError::DecodingError(
+                "Cannot compute the time range of a file without objects".to_owned(),
+            )),
+        }
+    }
+
+    #[must_use]
+    /// Returns the optional format features the file declares in its trailer.
+    ///
+    /// Files with the historical `0xDEADBEEF` trailer report no capability.
+    pub fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+
+    /// Decodes the top-level objects and keys them by their `ObjectId`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be decoded
+    pub fn decode_by_id(&mut self) -> Result<IndexMap<ObjectId, Value>, SpudError> {
+        let object_ids: Vec<ObjectId> = self.object_ids()?;
+        let objects: Vec<IndexMap<String, Value>> = self.decode_objects()?;
+
+        Ok(object_ids
+            .into_iter()
+            .zip(objects)
+            .map(|(object_id, object)| (object_id, Value::Object(object.into_iter().collect())))
+            .collect())
+    }
+
+    /// Returns an iterator decoding the top-level objects one at a time, along with their offset in the file.
+    ///
+    /// The offset is the position of the object's `ObjectStart ObjectStart` bytes in the original file,
+    /// header included, so a single pass is enough to build an index for later random access.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "sync")] {
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("value", 42u8)?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+    ///
+    /// let decoder = SpudDecoder::new(&encoded_bytes).unwrap();
+    ///
+    /// for object in decoder.objects() {
+    ///     let (offset, fields) = object.unwrap();
+    ///
+    ///     assert_eq!(encoded_bytes[offset], 0x12);
+    ///     assert_eq!(fields["value"], 42);
+    /// }
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn objects(&self) -> SpudObjectIter<'_> {
+        SpudObjectIter::new(self)
+    }
+
+    pub(crate) fn body_offset(&self) -> usize {
+        self.body_offset
+    }
+
+    /// Decodes every top-level object into a `serde_json::Value`, without serializing them to a string.
+    ///
+    /// The header is parsed once in `SpudDecoder::new`, so a decoder built from a file can be kept
+    /// around and decoded repeatedly without re-reading it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be decoded
+    pub fn decode_values(&self) -> Result<Vec<Value>, SpudError> {
+        Ok(self
+            .decode_objects()?
+            .into_iter()
+            .map(|object| Value::Object(object.into_iter().collect()))
+            .collect())
+    }
+
+    /// Decodes every top-level object on its own, so a corrupted object does not prevent reading the others.
+    ///
+    /// In files written with `with_object_checksums`, an object whose bytes no longer match its checksum
+    /// is reported as a `SpudError::DecodingError` at its position in the list, while the other objects
+    /// decode as usual. Other decoding errors are reported per object the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "sync")] {
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder};
+    ///
+    /// let mut builder = SpudBuilderSync::new();
+    ///
+    /// builder.with_object_checksums();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("name", "Alice")?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+    ///
+    /// let decoder = SpudDecoder::new(&encoded_bytes).unwrap();
+    ///
+    /// assert!(decoder.decode_checked().unwrap().iter().all(Result::is_ok));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the objects cannot be located in the file
+    pub fn decode_checked(&self) -> Result<Vec<Result<Value, SpudError>>, SpudError> {
+        Ok(self
+            .object_ranges()?
+            .into_iter()
+            .map(|range| {
+                self.decode_object_at(range)
+                    .map(|object| Value::Object(object.into_iter().collect()))
+            })
+            .collect())
+    }
+
+    /// Decodes every top-level object and returns them as CBOR, an array holding one map per object.
+    ///
+    /// Values keep their SPUD type rather than their JSON rendering: binary blobs become byte strings,
+    /// `F32` and `F64` become single and double precision floats, and integers past 64 bits become bignums.
+    /// Strings, dates, times, decimals, enums and ids are written as text, following the decoder's options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "sync")] {
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder, types::BinaryBlob};
+    ///
+    /// let builder = SpudBuilderSync::new();
+    ///
+    /// builder.object(|obj| {
+    ///     obj.add_value("data", BinaryBlob::new(&[0xCA, 0xFE]))?;
+    ///
+    ///     Ok(())
+    /// }).unwrap();
+    ///
+    /// let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+    ///
+    /// let cbor: Vec<u8> = SpudDecoder::new(&encoded_bytes).unwrap().build_cbor().unwrap();
+    ///
+    /// assert!(cbor.ends_with(&[0x64, b'd', b'a', b't', b'a', 0x42, 0xCA, 0xFE]));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be decoded
+    #[cfg(feature = "cbor")]
+    pub fn build_cbor(&self) -> Result<Vec<u8>, SpudError> {
+        let options: DecoderOptions = DecoderOptions {
+            annotate_types: true,
+            blob_encoding: BlobEncoding::Array,
+            ..self.options.clone()
+        };
+
+        let ranges: Vec<Range<usize>> = self.object_ranges()?;
+
+        let mut output: Vec<u8> = Vec::new();
+
+        cbor::write_head(cbor::MAJOR_ARRAY, ranges.len() as u64, &mut output);
+
+        for range in ranges {
+            self.verify_checksum(&range)?;
+
+            let object: IndexMap<String, Value> =
+                DecoderObject::new(&self.file_contents[range], &self.field_names, &options)
+                    .decode()?;
+
+            cbor::write_object(&object, &mut output)?;
+        }
+
+        Ok(output)
+    }
+
+    /// Decodes the SPUD file contents straight into a type implementing `serde::Deserialize`.
+    ///
+    /// The value has the same shape as `decode(false, false)`: a single object when the file holds one,
+    /// an array of objects otherwise. Every object keeps its `oid` field, which is ignored unless `T`
+    /// denies unknown fields, and can be captured with an `oid: Option<String>` field.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SpudError::DecodingError` if the file cannot be decoded or does not match the shape of `T`
+    #[cfg(feature = "serde")]
+    pub fn decode_into<T: DeserializeOwned>(&self) -> Result<T, SpudError> {
+        serde_json::from_value(self.decode_to_value(false)?).map_err(|err| {
+            SpudError::DecodingError(format!("Failed to deserialize decoded value: {err}"))
+        })
+    }
+
+    /// Extracts the top-level object at `index` as a standalone SPUD file.
+    ///
+    /// The object bytes are copied as they are, and the new header only lists the field names
+    /// the object uses, nested objects included, keeping their original ids.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the top-level object to extract.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no object at `index` or if the object cannot be decoded
+    pub fn extract_object(&self, index: usize) -> Result<Vec<u8>, SpudError> {
+        let range: Range<usize> =
+            self.object_ranges()?
+                .into_iter()
+                .nth(index)
+                .ok_or_else(|| {
+                    SpudError::DecodingError(format!("Object at index {index} not found"))
+                })?;
+
+        let object_bytes: &[u8] = &self.file_contents[range.clone()];
+
+        let options: DecoderOptions = DecoderOptions {
+            annotate_types: true,
+            object_versions: self.options.object_versions,
+            ..DecoderOptions::default()
+        };
+
+        let object: IndexMap<String, Value> =
+            DecoderObject::new(object_bytes, &self.field_names, &options).decode()?;
+
+        let mut used_names: Vec<&str> = Vec::new();
+
+        collect_field_names(object.iter(), &mut used_names);
+
+        let mut file: Vec<u8> = SPUD_VERSION.as_bytes().to_vec();
+
+        for (id, name) in &self.field_names {
+            if used_names.contains(&name.as_str()) {
+                file.push(u8::try_from(name.len())?);
+                file.extend_from_slice(name.as_bytes());
+                file.push(*id);
+            }
+        }
+
+        file.push(SpudTypes::FieldNameListEnd.as_u8());
+        file.extend_from_slice(&self.file_contents[range.start..range.end + self.checksum_len()]);
+        let mut capabilities: Capabilities = self.capabilities();
+
+        // The extracted file carries its names in the header
+        capabilities.remove(Capabilities::FOOTER_FIELD_TABLE);
+
+        file.extend_from_slice(&capabilities.as_trailer());
+
+        Ok(file)
+    }
+
+    /// Compares the decoded contents of two files, ignoring object ids and the order of fields within objects.
+    ///
+    /// The top-level objects are compared in file order.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The decoder of the file to compare against.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either file cannot be decoded
+    pub fn structurally_equal(&self, other: &SpudDecoder) -> Result<bool, SpudError> {
+        let mut own_values: Vec<Value> = self.decode_values()?;
+        let mut other_values: Vec<Value> = other.decode_values()?;
+
+        own_values.iter_mut().for_each(strip_ids);
+        other_values.iter_mut().for_each(strip_ids);
+
+        Ok(own_values == other_values)
+    }
+
+    /// Compares two encoded SPUD files by their decoded contents rather than their bytes.
+    ///
+    /// Field ids, which are picked at random by default, and the order of fields within objects are
+    /// ignored, and so are object ids when `ignore_oids` is set. The top-level objects are compared in
+    /// file order. Meant for tests asserting that two encoders produce equivalent data, decoding can
+    /// fail, which is why this is a function rather than a `PartialEq` implementation.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The bytes of the first file.
+    /// * `b` - The bytes of the second file.
+    /// * `ignore_oids` - Whether objects with different ids but equal fields count as equal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "sync")] {
+    /// use spud_rs::{SpudBuilderSync, SpudDecoder};
+    ///
+    /// let encode = || {
+    ///     let builder = SpudBuilderSync::new();
+    ///
+    ///     builder.object(|obj| {
+    ///         obj.add_value("name", "Alice")?;
+    ///
+    ///         Ok(())
+    ///     }).unwrap();
+    ///
+    ///     builder.encode().unwrap()
+    /// };
+    ///
+    /// assert!(SpudDecoder::semantic_eq(&encode(), &encode(), true).unwrap());
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either buffer is not a valid SPUD file or cannot be decoded
+    pub fn semantic_eq(a: &[u8], b: &[u8], ignore_oids: bool) -> Result<bool, SpudError> {
+        let (a, b) = (SpudDecoder::new(a)?, SpudDecoder::new(b)?);
+
+        if ignore_oids {
+            return a.structurally_equal(&b);
+        }
+
+        Ok(a.decode_values()? == b.decode_values()?)
+    }
+
+    /// Returns a SHA-256 fingerprint of the decoded contents, for caching and change detection.
+    ///
+    /// Like `structurally_equal`, object ids and the order of fields within objects are ignored:
+    /// the hash covers the decoded objects with their ids stripped and their keys sorted, so two files
+    /// holding the same values fingerprint equally however they were built.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be decoded
+    pub fn fingerprint(&self) -> Result<[u8; 32], SpudError> {
+        let mut values: Vec<Value> = self.decode_values()?;
+
+        values.iter_mut().for_each(strip_ids);
+
+        let canonical: Value = Value::Array(values.into_iter().map(sort_keys).collect());
+
+        Ok(sha256(&serde_json::to_vec(&canonical)?))
+    }
+
+    /// Decodes every top-level object into a caller-owned `Vec`, replacing its contents.
+    ///
+    /// The vector's capacity is reused across calls, the objects themselves are freshly allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The vector the objects are pushed to, cleared first. On error it holds the objects decoded before the failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be decoded
+    pub fn decode_values_into(
+        &self,
+        out: &mut Vec<IndexMap<String, Value>>,
+    ) -> Result<(), SpudError> {
+        out.clear();
+
+        for range in self.object_ranges()? {
+            out.push(self.decode_object_at(range)?);
+        }
+
+        Ok(())
+    }
+
+    fn decode_objects(&self) -> Result<Vec<IndexMap<String, Value>>, SpudError> {
+        let mut decoded_objects: Vec<IndexMap<String, Value>> = Vec::new();
+
+        self.decode_values_into(&mut decoded_objects)?;
+
+        Ok(decoded_objects)
+    }
+
+    /// Decodes the top-level object in `range`, after checking its checksum if the file has them.
+    pub(crate) fn decode_object_at(
+        &self,
+        range: Range<usize>,
+    ) -> Result<IndexMap<String, Value>, SpudError> {
+        self.verify_checksum(&range)?;
+
+        DecoderObject::new(&self.file_contents[range], &self.field_names, &self.options).decode()
+    }
+
+    /// Compares the CRC-32 stored after the object in `range` with the one computed from its bytes.
+    fn verify_checksum(&self, range: &Range<usize>) -> Result<(), SpudError> {
+        if !self.capabilities.contains(Capabilities::CHECKSUM) {
+            return Ok(());
+        }
+
+        let offset: usize = self.body_offset + range.start;
+
+        let stored: [u8; CHECKSUM_LEN] = self
+            .file_contents
+            .get(range.end..range.end + CHECKSUM_LEN)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                SpudError::DecodingError(format!(
+                    "missing checksum after the object starting at byte {offset}"
+                ))
+            })?;
+
+        let stored: u32 = u32::from_le_bytes(stored);
+        let computed: u32 = crc32(&self.file_contents[range.clone()]);
+
+        if stored != computed {
+            return Err(SpudError::DecodingError(format!(
+                "checksum mismatch in the object starting at byte {offset}: stored {stored:#010X}, computed {computed:#010X}"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// The number of bytes following every top-level object, 4 when the file has checksums and 0 otherwise.
+    pub(crate) fn checksum_len(&self) -> usize {
+        if self.capabilities.contains(Capabilities::CHECKSUM) {
+            CHECKSUM_LEN
+        } else {
+            0
+        }
+    }
+
+    /// Walks the file contents object by object and returns the byte range of every top-level object,
+    /// checksums excluded.
+    pub(crate) fn object_ranges(&self) -> Result<Vec<Range<usize>>, SpudError> {
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        let mut i: usize = 0;
+
+        while let Some(range) = self.next_object_range(i)? {
+            i = range.end + self.checksum_len();
+
+            ranges.push(range);
+        }
+
+        Ok(ranges)
+    }
+
+    /// Returns the byte range of the first top-level object starting at or after `from`, if any.
+    pub(crate) fn next_object_range(&self, from: usize) -> Result<Option<Range<usize>>, SpudError> {
+        let mut i: usize = from;
+
+        while i < self.file_contents.len() {
+            if self.file_contents.get(i) == Some(&SpudTypes::ObjectStart.as_u8())
+                && self.file_contents.get(i + 1) == Some(&SpudTypes::ObjectStart.as_u8())
+            {
+                let mut object: DecoderObject<'_> =
+                    DecoderObject::new(&self.file_contents[i..], &self.field_names, &self.options);
+
+                let len: usize = object_len(&mut object, i)?;
+
+                return Ok(Some(i..i + len));
+            }
+
+            i += 1;
+        }
+
+        Ok(None)
+    }
+}
+
+/// Steps over the object at the start of `object` token by token, without decoding its values, and returns
+/// its length up to and including the closing `ObjectEnd` pair.
+///
+/// Payload bytes are skipped by their type's width rather than matched, so a value ending in `0x13` cannot be
+/// mistaken for the end of its object. Open objects and arrays are counted rather than recursed into, keeping
+/// malicious nesting from exhausting the stack.
+fn object_len(object: &mut DecoderObject, start: usize) -> Result<usize, SpudError> {
+    let unterminated =
+        || SpudError::DecodingError(format!("unterminated object starting at byte {start}"));
+
+    object.read_object_header()?;
+
+    // `true` for an open object, `false` for an open array
+    let mut open: Vec<bool> = vec![true];
+
+    loop {
+        let Some(&byte) = object.contents.get(object.index) else {
+            return Err(unterminated());
+        };
+
+        let unknown_type = || {
+            SpudError::DecodingError(format!(
+                "Unknown type: {byte} at index {}",
+                start + object.index
+            ))
+        };
+
+        let steps: usize = match SpudTypes::from_u8(byte) {
+            Some(SpudTypes::ObjectEnd)
+                if open.last() == Some(&true)
+                    && object.contents.get(object.index + 1) == Some(&byte) =>
+            {
+                open.pop();
+
+                if open.is_empty() {
+                    return Ok(object.index + 2);
+                }
+
+                2
+            }
+            Some(SpudTypes::ArrayEnd) if open.last() == Some(&false) => {
+                open.pop();
+
+                1
+            }
+            Some(SpudTypes::ObjectStart) => {
+                open.push(true);
+
+                // Both `ObjectStart` bytes and the id, nested objects carry no version byte
+                12
+            }
+            Some(SpudTypes::ArrayStart) => {
+                open.push(false);
+
+                1
+            }
+            Some(SpudTypes::IpAddr) => {
+                let Some(&version) = object.contents.get(object.index + 1) else {
+                    return Err(unterminated());
+                };
+
+                // The tag, the version byte and the address
+                ip_addr_width(version)? + 2
+            }
+            // The length prefix is read here, leaving the cursor on the first byte of the payload
+            Some(SpudTypes::String | SpudTypes::BinaryBlob) => object
+                .read_variable_length_data()
+                .map_err(|_| unterminated())?,
+            Some(spud_type) => fixed_width(spud_type).ok_or_else(unknown_type)? + 1,
+            None => Err(unknown_type())?,
+        };
+
+        if open.len() > MAX_SCAN_DEPTH {
+            return Err(SpudError::DecodingError(format!(
+                "object starting at byte {start} is nested deeper than {MAX_SCAN_DEPTH} levels"
+            )));
+        }
+
+        object.next(steps).map_err(|_| unterminated())?;
+    }
+}
+
+/// The payload width of a type whose values always take the same number of bytes, including the field
+/// name id following a `FieldNameId` token.
+fn fixed_width(spud_type: SpudTypes) -> Option<usize> {
+    match spud_type {
+        SpudTypes::Null => Some(0),
+        SpudTypes::Bool | SpudTypes::Enum | SpudTypes::FieldNameId => Some(1),
+        SpudTypes::Number(number_type) => Some(number_type.width()),
+        SpudTypes::Char | SpudTypes::Date => Some(4),
+        SpudTypes::Time => Some(7),
+        SpudTypes::DateTime => Some(11),
+        SpudTypes::Duration => Some(12),
+        SpudTypes::Decimal | SpudTypes::Uuid => Some(16),
+        _ => None,
+    }
+}
+
+/// Collects the field names of an object decoded with `annotate_types`, along with the enum variant names it uses.
+fn collect_field_names<'a>(
+    fields: impl Iterator<Item = (&'a String, &'a Value)>,
+    names: &mut Vec<&'a str>,
+) {
+    for (name, value) in fields {
+        if !names.contains(&name.as_str()) {
+            names.push(name);
+        }
+
+        collect_nested_field_names(value, names);
+    }
+}
+
+fn collect_nested_field_names<'a>(value: &'a Value, names: &mut Vec<&'a str>) {
+    let (Some(Value::String(spud_type)), Some(value)) = (value.get("type"), value.get("value"))
+    else {
+        return;
+    };
+
+    match (spud_type.as_str(), value) {
+        ("Enum", Value::String(variant)) if !names.contains(&variant.as_str()) => {
+            names.push(variant);
+        }
+        ("Object", Value::Object(object)) => collect_field_names(object.iter(), names),
+        ("Array", Value::Array(items)) => {
+            for item in items {
+                collect_nested_field_names(item, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn strip_ids(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.remove("oid");
+            object.values_mut().for_each(strip_ids);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_ids),
+        _ => {}
+    }
+}
+
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<(String, Value)> = object.into_iter().collect();
+
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+fn flatten_object(
+    path: &str,
+    object: impl IntoIterator<Item = (String, Value)>,
+    lines: &mut Vec<Map<String, Value>>,
+) {
+    let line_index: usize = lines.len();
+
+    let mut line: Map<String, Value> = Map::new();
+    line.insert("_path".to_owned(), Value::String(path.to_owned()));
+
+    lines.push(Map::new());
+
+    for (field, value) in object {
+        if let Some(value) = flatten_value(&format!("{path}.{field}"), value, lines) {
+            line.insert(field, value);
+        }
+    }
+
+    lines[line_index] = line;
+}
+
+fn flatten_value(path: &str, value: Value, lines: &mut Vec<Map<String, Value>>) -> Option<Value> {
+    match value {
+        Value::Object(object) => {
+            flatten_object(path, object, lines);
+
+            None
+        }
+        Value::Array(values) => Some(Value::Array(
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    let path: String = format!("{path}[{index}]");
+
+                    // Objects move to their own line, a reference keeps the other elements at their index
+                    flatten_value(&path, value, lines).unwrap_or_else(|| {
+                        Value::Object(Map::from_iter([("_ref".to_owned(), Value::String(path))]))
+                    })
+                })
+                .collect(),
+        )),
+        value => Some(value),
+    }
+}
+
+fn flatten_keys(path: String, value: Value, separator: &str, flat: &mut IndexMap<String, Value>) {
+    match value {
+        Value::Object(object) if !object.is_empty() => {
+            for (field, value) in object {
+                flatten_keys(format!("{path}{separator}{field}"), value, separator, flat);
+            }
+        }
+        Value::Array(values) if !values.is_empty() => {
+            for (index, value) in values.into_iter().enumerate() {
+                flatten_keys(format!("{path}[{index}]"), value, separator, flat);
+            }
+        }
+        value => {
+            flat.insert(path, value);
+        }
+    }
+}
+
+#[cfg(feature = "sync")]
+impl SpudDecoder {
+    /// Creates a new `SpudDecoder` instance from a file at the specified path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the file to read.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the path is invalid
     ///
     /// # Errors
     ///
     /// Will return an error if the path is invalid
-    pub async fn new_from_path_async(path: &str) -> Result<Self, SpudError> {
-        let file: Vec<u8> = tokio_read(path).await?;
+    ///
+    /// # Notes
+    ///
+    /// There is an async version of this function available if the `async` feature is enabled.
+    pub fn new_from_path(path: &str) -> Result<Self, SpudError> {
+        Self::from_vec(std_read(path)?)
+    }
+
+    /// Creates a new `SpudDecoder` instance by reading a SPUD file from a stream.
+    ///
+    /// The header is read one field-name entry at a time, stopping at the `FieldNameListEnd` byte,
+    /// and only then are the object bytes read, so the file is never held in memory twice.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The stream to read the file from, e.g. a `File` or a network stream.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the stream cannot be read or is not a valid spud file
+    pub fn new_from_reader<R: Read>(mut reader: R) -> Result<Self, SpudError> {
+        let mut header: Vec<u8> = Self::read_header(&mut reader)?;
+
+        let (field_names, body_start): (IndexMap<u8, String>, usize) = Self::parse_header(&header)?;
+
+        let mut body: Vec<u8> = header.split_off(body_start);
+
+        reader.read_to_end(&mut body)?;
+
+        Self::from_parts(body, field_names, body_start)
+    }
+
+    /// Reads the header of the SPUD file at `path`, without reading its objects.
+    ///
+    /// Only the version, the field-name table and the 4-byte trailer are read, so surveying many
+    /// large files costs a few small reads per file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the file to read.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the file cannot be read or does not start with a valid SPUD header
+    pub fn read_header_only(path: &str) -> Result<HeaderInfo, SpudError> {
+        Self::read_header_info(&mut BufReader::new(StdFile::open(path)?))
+    }
+
+    pub(crate) fn read_header_info<R: Read + Seek>(
+        reader: &mut R,
+    ) -> Result<HeaderInfo, SpudError> {
+        let header: Vec<u8> = Self::read_header(reader)?;
+
+        let (field_names, _): (IndexMap<u8, String>, usize) = Self::parse_header(&header)?;
+
+        let mut trailer: [u8; 4] = [0; 4];
+
+        reader.seek(SeekFrom::End(-4))?;
+        reader.read_exact(&mut trailer)?;
+
+        let capabilities: Capabilities = Capabilities::from_trailer(trailer)?;
+
+        if !capabilities.contains(Capabilities::FOOTER_FIELD_TABLE) {
+            return Ok(HeaderInfo::new(
+                SPUD_VERSION.to_owned(),
+                field_names,
+                capabilities,
+            ));
+        }
+
+        let mut offset: [u8; FOOTER_OFFSET_LEN] = [0; FOOTER_OFFSET_LEN];
+
+        reader.seek(SeekFrom::End(-i64::try_from(4 + FOOTER_OFFSET_LEN)?))?;
+        reader.read_exact(&mut offset)?;
+        reader.seek(SeekFrom::Start(u64::from_le_bytes(offset)))?;
+
+        let mut table: Vec<u8> = Vec::new();
+
+        Self::read_field_table(reader, &mut table)?;
+
+        let (field_names, _): (IndexMap<u8, String>, usize) = Self::parse_field_table(&table)?;
+
+        Ok(HeaderInfo::new(
+            SPUD_VERSION.to_owned(),
+            field_names,
+            capabilities,
+        ))
+    }
+
+    /// Reads the version and the field name list one entry at a time, stopping right after `FieldNameListEnd`.
+    fn read_header<R: Read>(reader: &mut R) -> Result<Vec<u8>, SpudError> {
+        let mut header: Vec<u8> = vec![0; SPUD_VERSION.len()];
+
+        reader.read_exact(&mut header)?;
 
-        Self::new(&file)
+        if header != SPUD_VERSION.as_bytes() {
+            return Err(SpudError::DecodingError(
+                "Invalid SPUD file: version mismatch".to_owned(),
+            ));
+        }
+
+        Self::read_field_table(reader, &mut header)?;
+
+        Ok(header)
+    }
+
+    /// Appends a field name list read one entry at a time to `out`, stopping right after `FieldNameListEnd`.
+    fn read_field_table<R: Read>(reader: &mut R, out: &mut Vec<u8>) -> Result<(), SpudError> {
+        let mut byte: [u8; 1] = [0];
+
+        loop {
+            reader.read_exact(&mut byte)?;
+            out.push(byte[0]);
+
+            if byte[0] == SpudTypes::FieldNameListEnd.as_u8() {
+                return Ok(());
+            }
+
+            let entry_start: usize = out.len();
+
+            out.resize(entry_start + usize::from(byte[0]) + 1, 0);
+
+            reader.read_exact(&mut out[entry_start..])?;
+        }
+    }
+
+    /// Builds a JSON file at the specified path with the given file name.
+    ///  # Arguments
+    ///
+    /// * `path_str` - The path to the directory where the file will be created.
+    /// * `file_name` - The name of the file to create.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file has errors being written
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the file has errors being written
+    ///
+    /// # Notes
+    ///
+    /// There is an async version of this function available if the `async` feature is enabled.
+    pub fn build_file(&self, path: &str) -> Result<(), SpudError> {
+        StdFile::create(Path::new(path))?.write_all(self.output_json.as_bytes())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl SpudDecoder {
+    /// Creates a new `SpudDecoder` instance from a file at the specified path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the file to read.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the path is invalid
+    pub async fn new_from_path_async(path: &str) -> Result<Self, SpudError> {
+        Self::from_vec(tokio_read(path).await?)
     }
 
     /// Builds a JSON file at the specified path with the given file name.
@@ -1,6 +1,10 @@
 use serde_json::Value;
 
-use crate::{SpudError, spud_decoder::DecoderObject, spud_types::SpudTypes};
+use crate::{
+    SpudError,
+    spud_decoder::DecoderObject,
+    spud_types::{SpudNumberTypes, SpudTypes},
+};
 
 pub(crate) fn array_start(
     decoder: &mut DecoderObject,
@@ -9,15 +13,29 @@ pub(crate) fn array_start(
     decoder.next(1)?;
 
     let mut output_array: Vec<Value> = vec![];
+    let mut byte_values: Option<Vec<u8>> = decoder.options.coalesce_byte_arrays.then(Vec::new);
 
     loop {
-        let byte: Option<SpudTypes> = SpudTypes::from_u8(decoder.contents[decoder.index]);
+        let Some(&byte) = decoder.contents.get(decoder.index) else {
+            return Err(SpudError::DecodingError(
+                "unterminated array: the buffer ended before ArrayEnd".to_owned(),
+            ));
+        };
 
-        if byte == Some(SpudTypes::ArrayEnd) {
+        if SpudTypes::from_u8(byte) == Some(SpudTypes::ArrayEnd) {
             break;
         }
 
-        let decoded_byte: Option<Value> = decoder.decode_byte(decoder.contents[decoder.index])?;
+        if let Some(bytes) = &mut byte_values {
+            match decoder.contents.get(decoder.index + 1) {
+                Some(&value) if byte == SpudTypes::Number(SpudNumberTypes::U8).as_u8() => {
+                    bytes.push(value);
+                }
+                _ => byte_values = None,
+            }
+        }
+
+        let decoded_byte: Option<Value> = decoder.decode_byte(byte)?;
 
         if let Some(value) = decoded_byte {
             output_array.push(value);
@@ -26,6 +44,12 @@ pub(crate) fn array_start(
 
     *next_steps = 1;
 
+    if let Some(bytes) = byte_values
+        && !bytes.is_empty()
+    {
+        return Ok(decoder.options.blob_encoding.render(&bytes));
+    }
+
     Ok(Value::Array(output_array))
 }
 
@@ -52,6 +76,96 @@ mod tests {
         decoder.decode(false, false).unwrap();
     }
 
+    #[test]
+    fn test_array_unterminated() {
+        use indexmap::IndexMap;
+
+        use crate::{
+            spud_decoder::{DecoderObject, DecoderOptions},
+            spud_types::{SpudNumberTypes, SpudTypes},
+        };
+
+        let mut contents: Vec<u8> = vec![
+            SpudTypes::ObjectStart.as_u8(),
+            SpudTypes::ObjectStart.as_u8(),
+        ];
+
+        contents.extend_from_slice(&[0; 10]);
+        contents.extend_from_slice(&[
+            SpudTypes::FieldNameId.as_u8(),
+            2,
+            SpudTypes::ArrayStart.as_u8(),
+            SpudTypes::Number(SpudNumberTypes::U8).as_u8(),
+            1,
+        ]);
+
+        let field_names: IndexMap<u8, String> = IndexMap::from([(2, "array".to_owned())]);
+        let options: DecoderOptions = DecoderOptions::default();
+
+        let mut decoder: DecoderObject<'_> = DecoderObject::new(&contents, &field_names, &options);
+
+        let Err(SpudError::DecodingError(message)) = decoder.decode() else {
+            panic!("expected a decoding error");
+        };
+
+        assert!(message.contains("unterminated array"));
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_array_streamed() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.array("readings", |arr| {
+                    arr.extend(0..100_000_u32)?;
+                    Ok(())
+                })?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        let readings: &Vec<serde_json::Value> = output["readings"].as_array().unwrap();
+
+        assert_eq!(readings.len(), 100_000);
+        assert_eq!(readings[99_999], 99_999);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_array_appended() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.append_to_array("tags", &[1u8, 2u8])?;
+                obj.append_to_array("other", &[9u8])?;
+                obj.add_value("name", 5u8)?;
+                obj.append_to_array("tags", &[3u8, 4u8])?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(output["tags"], serde_json::json!([1, 2, 3, 4]));
+        assert_eq!(output["other"], serde_json::json!([9]));
+        assert_eq!(output["name"], 5);
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn test_array_async() {
@@ -77,4 +191,68 @@ mod tests {
 
         decoder.decode(false, false).unwrap();
     }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_array_streamed_async() {
+        use std::sync::Arc;
+
+        use tokio::sync::{Mutex, MutexGuard};
+
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.array("readings", async |arr: Arc<Mutex<SpudArrayAsync>>| {
+                    arr.lock().await.extend(0..1_000_u32).await?;
+                    Ok(())
+                })
+                .await?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().await.unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(output["readings"].as_array().unwrap().len(), 1_000);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_array_appended_async() {
+        use std::sync::Arc;
+
+        use tokio::sync::{Mutex, MutexGuard};
+
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.append_to_array("tags", &[1u8, 2u8]).await?;
+                obj.add_value("name", 5u8).await?;
+                obj.append_to_array("tags", &[3u8, 4u8]).await?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().await.unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(output["tags"], serde_json::json!([1, 2, 3, 4]));
+    }
 }
@@ -1,4 +1,4 @@
-use serde_json::{Number, Value};
+use serde_json::Value;
 
 use crate::{SpudError, spud_decoder::DecoderObject};
 
@@ -8,17 +8,11 @@ pub(crate) fn binary_blob(
 ) -> Result<Value, SpudError> {
     let blob_len: usize = decoder.read_variable_length_data()?;
 
-    let processed: Vec<u8> = decoder.contents[decoder.index..decoder.index + blob_len].to_vec();
-
-    let mut output_array: Vec<Value> = vec![];
-
-    for processed_byte in &processed {
-        output_array.push(Value::Number(Number::from(*processed_byte)));
-    }
+    let processed: &[u8] = decoder.peek_bytes(blob_len)?;
 
     *next_steps = blob_len;
 
-    Ok(Value::Array(output_array))
+    Ok(decoder.options.blob_encoding.render(processed))
 }
 
 #[cfg(test)]
@@ -44,6 +38,46 @@ mod tests {
         decoder.decode(false, false).unwrap();
     }
 
+    #[test]
+    fn test_blob_length_beyond_32_bits() {
+        use indexmap::IndexMap;
+
+        use crate::{
+            spud_decoder::{DecoderObject, DecoderOptions},
+            spud_types::{SpudNumberTypes, SpudTypes},
+        };
+
+        let mut contents: Vec<u8> = vec![
+            SpudTypes::ObjectStart.as_u8(),
+            SpudTypes::ObjectStart.as_u8(),
+        ];
+
+        contents.extend_from_slice(&[0; 10]);
+        contents.extend_from_slice(&[
+            SpudTypes::FieldNameId.as_u8(),
+            2,
+            SpudTypes::BinaryBlob.as_u8(),
+            SpudTypes::Number(SpudNumberTypes::U64).as_u8(),
+        ]);
+        contents.extend_from_slice(&(u64::from(u32::MAX) + 1).to_le_bytes());
+        contents.extend_from_slice(&[SpudTypes::ObjectEnd.as_u8(), SpudTypes::ObjectEnd.as_u8()]);
+
+        let field_names: IndexMap<u8, String> = IndexMap::from([(2, "blob".to_owned())]);
+        let options: DecoderOptions = DecoderOptions::default();
+
+        let mut decoder: DecoderObject<'_> = DecoderObject::new(&contents, &field_names, &options);
+
+        let Err(SpudError::DecodingError(message)) = decoder.decode() else {
+            panic!("expected a decoding error");
+        };
+
+        #[cfg(target_pointer_width = "64")]
+        assert!(message.starts_with("Index out of bounds"));
+
+        #[cfg(not(target_pointer_width = "64"))]
+        assert_eq!(message, "length exceeds platform addressable size");
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn test_blob_async() {
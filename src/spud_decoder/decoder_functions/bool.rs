@@ -11,9 +11,13 @@ pub(crate) fn bool(
     let value: Value = match decoder.contents.get(decoder.index) {
         Some(0) => Value::Bool(false),
         Some(1) => Value::Bool(true),
-        _ => Err(SpudError::DecodingError(format!(
-            "Unknown bool value: {}",
-            decoder.contents[decoder.index]
+        Some(byte) => Err(SpudError::DecodingError(format!(
+            "Unknown bool value {byte} at index {}",
+            decoder.index
+        )))?,
+        None => Err(SpudError::DecodingError(format!(
+            "Missing bool value at index {}",
+            decoder.index
         )))?,
     };
 
@@ -70,4 +74,36 @@ mod tests {
 
         decoder.decode(false, false).unwrap();
     }
+
+    #[test]
+    fn test_bool_invalid() {
+        use indexmap::IndexMap;
+
+        use crate::{
+            spud_decoder::{DecoderObject, DecoderOptions},
+            spud_types::SpudTypes,
+        };
+
+        let field_names: IndexMap<u8, String> = IndexMap::new();
+        let options: DecoderOptions = DecoderOptions::default();
+
+        let decode = |contents: &[u8]| {
+            let mut decoder: DecoderObject = DecoderObject::new(contents, &field_names, &options);
+
+            let Err(SpudError::DecodingError(message)) = super::bool(&mut decoder, &mut 0) else {
+                panic!("expected a decoding error");
+            };
+
+            message
+        };
+
+        assert_eq!(
+            decode(&[SpudTypes::Bool.as_u8()]),
+            "Missing bool value at index 1"
+        );
+        assert_eq!(
+            decode(&[SpudTypes::Bool.as_u8(), 2]),
+            "Unknown bool value 2 at index 1"
+        );
+    }
 }
@@ -0,0 +1,81 @@
+use serde_json::Value;
+
+use crate::{SpudError, spud_decoder::DecoderObject};
+
+pub(crate) fn char(decoder: &mut DecoderObject) -> Result<Value, SpudError> {
+    decoder.next(1)?;
+
+    let read_bytes: &[u8] = decoder.read_bytes(4)?;
+
+    let codepoint: u32 = u32::from_le_bytes(
+        read_bytes
+            .try_into()
+            .map_err(|_| SpudError::DecodingError("Invalid Char bytes".to_owned()))?,
+    );
+
+    let value: char = char::from_u32(codepoint).ok_or_else(|| {
+        SpudError::DecodingError(format!("Invalid char codepoint: {codepoint:#X}"))
+    })?;
+
+    Ok(Value::String(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_char() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("letter", 'é')?;
+                obj.add_value("emoji", '🥔')?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(output["letter"], "é");
+        assert_eq!(output["emoji"], "🥔");
+    }
+
+    #[test]
+    fn test_char_surrogate() {
+        use indexmap::IndexMap;
+
+        use crate::{
+            spud_decoder::{DecoderObject, DecoderOptions},
+            spud_types::SpudTypes,
+        };
+
+        let mut contents: Vec<u8> = vec![
+            SpudTypes::ObjectStart.as_u8(),
+            SpudTypes::ObjectStart.as_u8(),
+        ];
+
+        contents.extend_from_slice(&[0; 10]);
+        contents.extend_from_slice(&[SpudTypes::FieldNameId.as_u8(), 2, SpudTypes::Char.as_u8()]);
+        contents.extend_from_slice(&0xD800_u32.to_le_bytes());
+        contents.extend_from_slice(&[SpudTypes::ObjectEnd.as_u8(), SpudTypes::ObjectEnd.as_u8()]);
+
+        let field_names: IndexMap<u8, String> = IndexMap::from([(2, "letter".to_owned())]);
+        let options: DecoderOptions = DecoderOptions::default();
+
+        let mut decoder: DecoderObject<'_> = DecoderObject::new(&contents, &field_names, &options);
+
+        let Err(SpudError::DecodingError(message)) = decoder.decode() else {
+            panic!("expected a decoding error");
+        };
+
+        assert_eq!(message, "Invalid char codepoint: 0xD800");
+    }
+}
@@ -1,5 +1,5 @@
 use rust_decimal::Decimal;
-use serde_json::Value;
+use serde_json::{Map, Number, Value};
 
 use crate::{SpudError, spud_decoder::DecoderObject};
 
@@ -14,9 +14,54 @@ pub(crate) fn decimal(decoder: &mut DecoderObject) -> Result<Value, SpudError> {
             .map_err(|_| SpudError::DecodingError("Invalid Decimal bytes".to_owned()))?,
     );
 
+    if decoder.options.decimal_as_parts {
+        let mut parts: Map<String, Value> = Map::new();
+
+        parts.insert(
+            "unscaled".to_owned(),
+            Value::String(decimal_value.mantissa().to_string()),
+        );
+        parts.insert(
+            "scale".to_owned(),
+            Value::Number(decimal_value.scale().into()),
+        );
+
+        return Ok(Value::Object(parts));
+    }
+
+    if decoder.options.decimal_as_number
+        && let Some(number) = lossless_number(decimal_value)
+    {
+        return Ok(Value::Number(number));
+    }
+
     Ok(Value::String(decimal_value.to_string()))
 }
 
+/// Converts a decimal to a JSON number, if one holds the same value: an integer when the decimal
+/// has no fractional part and fits 64 bits, otherwise an `f64` that reads back as the same decimal.
+fn lossless_number(value: Decimal) -> Option<Number> {
+    if value.fract().is_zero() {
+        let integer: String = value.trunc().to_string();
+
+        if let Ok(integer) = integer.parse::<i64>() {
+            return Some(Number::from(integer));
+        }
+
+        if let Ok(integer) = integer.parse::<u64>() {
+            return Some(Number::from(integer));
+        }
+    }
+
+    let float: f64 = value.to_string().parse().ok()?;
+
+    if float.to_string().parse::<Decimal>().ok()? != value {
+        return None;
+    }
+
+    Number::from_f64(float)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{types::Decimal, *};
@@ -40,6 +85,68 @@ mod tests {
         decoder.decode(false, false).unwrap();
     }
 
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decimal_as_parts() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("decimal", Decimal::from_str_exact("1.50").unwrap())?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(output["decimal"], "1.50");
+
+        decoder.with_decimal_as_parts(true);
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(
+            output["decimal"],
+            serde_json::json!({"unscaled": "150", "scale": 2})
+        );
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decimal_as_number() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("price", Decimal::from_str_exact("1.25").unwrap())?;
+                obj.add_value("count", Decimal::from_str_exact("42.00").unwrap())?;
+                obj.add_value(
+                    "precise",
+                    Decimal::from_str_exact("12345678901234567890.123456789").unwrap(),
+                )?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        decoder.with_decimal_as_number(true);
+
+        let output: serde_json::Value = decoder.decode_to_value(false).unwrap();
+
+        assert_eq!(output["price"], serde_json::json!(1.25));
+        assert_eq!(output["count"], serde_json::json!(42));
+        assert_eq!(output["precise"], "12345678901234567890.123456789");
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn test_decimal_async() {
@@ -0,0 +1,93 @@
+use chrono::TimeDelta;
+use serde_json::Value;
+
+use crate::{SpudError, spud_decoder::DecoderObject};
+
+pub(crate) fn duration(decoder: &mut DecoderObject) -> Result<Value, SpudError> {
+    decoder.next(1)?;
+
+    let read_bytes: &[u8] = decoder.read_bytes(12)?;
+
+    let secs: i64 = i64::from_le_bytes(
+        read_bytes[0..8]
+            .try_into()
+            .map_err(|_| SpudError::DecodingError("Invalid Duration bytes".to_owned()))?,
+    );
+    let nanos: u32 = u32::from_le_bytes(
+        read_bytes[8..]
+            .try_into()
+            .map_err(|_| SpudError::DecodingError("Invalid Duration bytes".to_owned()))?,
+    );
+
+    let value: TimeDelta = TimeDelta::new(secs, nanos).ok_or_else(|| {
+        SpudError::DecodingError(format!("Invalid Duration: {secs}s and {nanos}ns"))
+    })?;
+
+    Ok(Value::String(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_duration() {
+        use std::time::Duration;
+
+        use chrono::TimeDelta;
+
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("timeout", TimeDelta::seconds(90))?;
+                obj.add_value("skew", TimeDelta::milliseconds(-1500))?;
+                obj.add_value("latency", Duration::from_micros(2500))?;
+                obj.add_value("window", "1h30m".parse::<types::Duration>()?)?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(objects[0]["timeout"], "PT90S");
+        assert_eq!(objects[0]["skew"], "-PT1.5S");
+        assert_eq!(objects[0]["latency"], "PT0.0025S");
+        assert_eq!(objects[0]["window"], "PT5400S");
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_duration_async() {
+        use std::sync::Arc;
+
+        use chrono::TimeDelta;
+        use tokio::sync::{Mutex, MutexGuard};
+
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.add_value("skew", TimeDelta::milliseconds(-1500))
+                    .await?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().await.unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(objects[0]["skew"], "-PT1.5S");
+    }
+}
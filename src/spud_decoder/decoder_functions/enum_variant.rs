@@ -0,0 +1,147 @@
+use serde_json::Value;
+
+use crate::{SpudError, spud_decoder::DecoderObject};
+
+pub(crate) fn enum_variant(
+    decoder: &mut DecoderObject,
+    next_steps: &mut usize,
+) -> Result<Value, SpudError> {
+    decoder.next(1)?;
+
+    let variant_id: u8 = decoder.peek_bytes(1)?[0];
+
+    let variant: String = decoder
+        .field_names
+        .get(&variant_id)
+        .cloned()
+        .ok_or_else(|| {
+            SpudError::DecodingError(format!(
+                "Enum variant ID {variant_id} not found in field names map"
+            ))
+        })?;
+
+    *next_steps = 1;
+
+    Ok(Value::String(variant))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_enum() {
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_enum("status", "suspended", &["active", "suspended", "closed"])?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(output["status"], "suspended");
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_enum_unknown_variant() {
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        let result: Result<(), SpudError> = builder.object(|obj| {
+            obj.add_enum("status", "deleted", &["active", "suspended", "closed"])?;
+            Ok(())
+        });
+
+        let Err(SpudError::EncodingError(message)) = result else {
+            panic!("expected an encoding error");
+        };
+
+        assert_eq!(message, "unknown enum variant 'deleted'");
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_enum_one_byte_per_value() {
+        let with_enum: SpudBuilderSync = SpudBuilderSync::new();
+        let with_u8: SpudBuilderSync = SpudBuilderSync::new();
+
+        let variants: [&str; 3] = ["active", "suspended", "closed"];
+
+        for i in 0..1000_usize {
+            with_enum
+                .object(|obj| {
+                    obj.add_enum("status", variants[i % 3], &variants)?;
+                    Ok(())
+                })
+                .unwrap();
+
+            with_u8
+                .object(|obj| {
+                    obj.add_value("status", 0u8)?;
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        assert_eq!(
+            with_enum.data.lock().unwrap().len(),
+            with_u8.data.lock().unwrap().len()
+        );
+
+        let encoded_bytes: Vec<u8> = with_enum.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let statuses: Vec<serde_json::Value> = decoder
+            .decode_values()
+            .unwrap()
+            .into_iter()
+            .map(|object| object["status"].clone())
+            .collect();
+
+        assert_eq!(statuses.len(), 1000);
+
+        for (i, status) in statuses.iter().enumerate() {
+            assert_eq!(status, variants[i % 3]);
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_enum_async() {
+        use std::sync::Arc;
+
+        use tokio::sync::{Mutex, MutexGuard};
+
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.add_enum("status", "closed", &["active", "suspended", "closed"])
+                    .await?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().await.unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(output["status"], "closed");
+    }
+}
@@ -0,0 +1,131 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use serde_json::Value;
+
+use crate::{SpudError, spud_decoder::DecoderObject};
+
+/// Returns the number of address bytes following an `IpAddr` version byte.
+///
+/// # Errors
+///
+/// Returns an error if the version is neither 4 nor 6.
+pub(crate) fn ip_addr_width(version: u8) -> Result<usize, SpudError> {
+    match version {
+        4 => Ok(4),
+        6 => Ok(16),
+        _ => Err(SpudError::DecodingError(format!(
+            "Invalid IpAddr version: {version}"
+        ))),
+    }
+}
+
+/// Decodes an address to its canonical text form, following the stored version even for IPv4-mapped IPv6 addresses.
+pub(crate) fn ip_addr(decoder: &mut DecoderObject) -> Result<Value, SpudError> {
+    decoder.next(1)?;
+
+    let version: u8 = decoder.read_bytes(1)?[0];
+
+    let read_bytes: &[u8] = decoder.read_bytes(ip_addr_width(version)?)?;
+
+    let address: String = if let Ok(octets) = <[u8; 4]>::try_from(read_bytes) {
+        Ipv4Addr::from(octets).to_string()
+    } else {
+        let octets: [u8; 16] = read_bytes
+            .try_into()
+            .map_err(|_| SpudError::DecodingError("Invalid IpAddr bytes".to_owned()))?;
+
+        Ipv6Addr::from(octets).to_string()
+    };
+
+    Ok(Value::String(address))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv6Addr};
+
+    use crate::*;
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_ip_addr() {
+        use std::net::Ipv4Addr;
+
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("v4", Ipv4Addr::new(192, 0, 2, 1))?;
+                obj.add_value("v6", "2001:db8::1".parse::<Ipv6Addr>().unwrap())?;
+                obj.add_value("mapped", Ipv4Addr::new(192, 0, 2, 1).to_ipv6_mapped())?;
+                obj.add_value("any", IpAddr::V4(Ipv4Addr::LOCALHOST))?;
+                // 0x13 bytes in the address must not be mistaken for the end of the object
+                obj.add_value("tail", Ipv4Addr::new(19, 19, 19, 19))?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(objects[0]["v4"], "192.0.2.1");
+        assert_eq!(objects[0]["v6"], "2001:db8::1");
+        assert_eq!(objects[0]["mapped"], "::ffff:192.0.2.1");
+        assert_eq!(objects[0]["any"], "127.0.0.1");
+        assert_eq!(objects[0]["tail"], "19.19.19.19");
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_ip_addr_async() {
+        use std::sync::Arc;
+
+        use tokio::sync::{Mutex, MutexGuard};
+
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.add_value("v6", IpAddr::V6(Ipv6Addr::LOCALHOST)).await?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().await.unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(objects[0]["v6"], "::1");
+    }
+
+    #[test]
+    fn test_ip_addr_invalid_version() {
+        use indexmap::IndexMap;
+
+        use crate::{
+            spud_decoder::{DecoderObject, DecoderOptions},
+            spud_types::SpudTypes,
+        };
+
+        let field_names: IndexMap<u8, String> = IndexMap::new();
+        let options: DecoderOptions = DecoderOptions::default();
+
+        let contents: Vec<u8> = vec![SpudTypes::IpAddr.as_u8(), 5, 127, 0, 0, 1];
+
+        let mut decoder: DecoderObject = DecoderObject::new(&contents, &field_names, &options);
+
+        let Err(SpudError::DecodingError(message)) = super::ip_addr(&mut decoder) else {
+            panic!("expected a decoding error");
+        };
+
+        assert_eq!(message, "Invalid IpAddr version: 5");
+    }
+}
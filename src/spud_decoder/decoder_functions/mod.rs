@@ -1,23 +1,33 @@
 mod array_start;
 mod binary_blob;
 mod bool;
+mod char;
 mod date;
 mod date_time;
 mod decimal;
+mod duration;
+mod enum_variant;
+mod ip_addr;
 mod null;
 mod number;
 mod object_start;
 mod string;
 mod time;
+mod uuid;
 
 pub(crate) use array_start::array_start;
 pub(crate) use binary_blob::binary_blob;
 pub(crate) use bool::bool;
+pub(crate) use char::char;
 pub(crate) use date::date;
 pub(crate) use date_time::date_time;
 pub(crate) use decimal::decimal;
+pub(crate) use duration::duration;
+pub(crate) use enum_variant::enum_variant;
+pub(crate) use ip_addr::{ip_addr, ip_addr_width};
 pub(crate) use null::null;
 pub(crate) use number::number;
 pub(crate) use object_start::object_start;
 pub(crate) use string::string;
 pub(crate) use time::time;
+pub(crate) use uuid::uuid;
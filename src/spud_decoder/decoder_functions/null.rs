@@ -1,9 +1,14 @@
 use serde_json::Value;
 
-pub(crate) fn null(next_steps: &mut usize) -> Value {
+use crate::spud_decoder::{DecoderObject, NullRepr};
+
+pub(crate) fn null(decoder: &DecoderObject, next_steps: &mut usize) -> Value {
     *next_steps = 1;
 
-    Value::Null
+    match &decoder.options.null_as {
+        NullRepr::String(sentinel) => Value::String(sentinel.clone()),
+        NullRepr::JsonNull | NullRepr::Omit => Value::Null,
+    }
 }
 
 #[cfg(test)]
@@ -29,6 +34,82 @@ mod tests {
         decoder.decode(false, false).unwrap();
     }
 
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_null_as() {
+        use serde_json::{Value, json};
+
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("name", "Alice")?;
+                obj.add_value("nickname", ())?;
+                obj.add_value("scores", vec![(), ()])?;
+                obj.object("address", |address| {
+                    address.add_value("city", ())?;
+                    Ok(())
+                })?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let value: Value = decoder.decode_to_value(false).unwrap();
+
+        assert_eq!(value["nickname"], Value::Null);
+        assert_eq!(value["scores"], json!([null, null]));
+        assert_eq!(value["address"]["city"], Value::Null);
+
+        decoder.with_null_as(NullRepr::String("N/A".to_owned()));
+
+        let value: Value = decoder.decode_to_value(false).unwrap();
+
+        assert_eq!(value["nickname"], "N/A");
+        assert_eq!(value["scores"], json!(["N/A", "N/A"]));
+        assert_eq!(value["address"]["city"], "N/A");
+
+        decoder.with_null_as(NullRepr::Omit);
+
+        let value: Value = decoder.decode_to_value(false).unwrap();
+
+        assert!(value.get("nickname").is_none());
+        assert_eq!(value["name"], "Alice");
+        assert_eq!(value["scores"], json!([null, null]));
+        assert!(value["address"].get("city").is_none());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_option() {
+        use serde_json::{Value, json};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("some", Some(42u8))?;
+                obj.add_value("none", None::<u8>)?;
+                obj.add_value("scores", vec![Some(1u8), None])?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let value: Value = decoder.decode_to_value(false).unwrap();
+
+        assert_eq!(value["some"], 42);
+        assert_eq!(value["none"], Value::Null);
+        assert_eq!(value["scores"], json!([1, null]));
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn test_null_async() {
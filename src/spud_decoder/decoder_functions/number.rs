@@ -82,26 +82,33 @@ pub(crate) fn number(
         SpudNumberTypes::F32 => {
             let read_bytes: &[u8] = decoder.read_bytes(4)?;
 
-            Number::from_f64(
-                f32::from_le_bytes(
-                    read_bytes
-                        .try_into()
-                        .map_err(|_| SpudError::DecodingError("Invalid F32 bytes".to_owned()))?,
-                )
-                .into(),
-            )
-            .ok_or(SpudError::DecodingError(
+            let value: f32 = f32::from_le_bytes(
+                read_bytes
+                    .try_into()
+                    .map_err(|_| SpudError::DecodingError("Invalid F32 bytes".to_owned()))?,
+            );
+
+            let value: f64 = if decoder.options.shortest_f32 {
+                shortest_f64(value)
+            } else {
+                value.into()
+            };
+
+            float_number(decoder, value).ok_or(SpudError::DecodingError(
                 "Invalid F32 value: cannot be NaN or infinity".to_owned(),
             ))?
         }
         SpudNumberTypes::F64 => {
             let read_bytes: &[u8] = decoder.read_bytes(8)?;
 
-            Number::from_f64(f64::from_le_bytes(
-                read_bytes
-                    .try_into()
-                    .map_err(|_| SpudError::DecodingError("Invalid F64 bytes".to_owned()))?,
-            ))
+            float_number(
+                decoder,
+                f64::from_le_bytes(
+                    read_bytes
+                        .try_into()
+                        .map_err(|_| SpudError::DecodingError("Invalid F64 bytes".to_owned()))?,
+                ),
+            )
             .ok_or(SpudError::DecodingError(
                 "Invalid F64 value: cannot be NaN or infinity".to_owned(),
             ))?
@@ -111,6 +118,25 @@ pub(crate) fn number(
     Ok(Value::Number(number))
 }
 
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn float_number(decoder: &DecoderObject, value: f64) -> Option<Number> {
+    if decoder.options.normalize_number_display
+        && value.fract() == 0.0
+        && value >= i64::MIN as f64
+        && value < i64::MAX as f64
+    {
+        return Some(Number::from(value as i64));
+    }
+
+    Number::from_f64(value)
+}
+
+/// Widens an `f32` to the `f64` closest to its shortest round-tripping decimal, so `3.15f32` becomes `3.15`
+/// rather than `3.1500000953674316`.
+fn shortest_f64(value: f32) -> f64 {
+    value.to_string().parse().unwrap_or(value.into())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
@@ -145,6 +171,63 @@ mod tests {
         decoder.decode(false, false).unwrap();
     }
 
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_number_normalize_display() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("f32", 42.0f32)?;
+                obj.add_value("f64", 42.0f64)?;
+                obj.add_value("fraction", 42.5f64)?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: &str = decoder.decode(false, false).unwrap();
+
+        assert!(output.contains(r#""f32":42.0,"f64":42.0,"fraction":42.5"#));
+
+        decoder.with_normalize_number_display(true);
+
+        let output: &str = decoder.decode(false, false).unwrap();
+
+        assert!(output.contains(r#""f32":42,"f64":42,"fraction":42.5"#));
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_number_shortest_f32() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("f32", 3.15f32)?;
+                obj.add_value("f64", 3.15f64)?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: &str = decoder.decode(false, false).unwrap();
+
+        assert!(output.contains(r#""f32":3.1500000953674316,"f64":3.15"#));
+
+        decoder.with_shortest_f32(true);
+
+        let output: &str = decoder.decode(false, false).unwrap();
+
+        assert!(output.contains(r#""f32":3.15,"f64":3.15"#));
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn test_number_async() {
@@ -11,22 +11,33 @@ pub(crate) fn object_start(
     let mut output_object: Map<String, Value> = Map::new();
 
     let id_bytes: &[u8] = decoder.read_bytes(10)?;
-    let object_id: String = bs58::encode(id_bytes).into_string();
-    output_object.insert("oid".to_string(), Value::String(object_id));
+
+    if !decoder.options.omit_oid {
+        let object_id: String = bs58::encode(id_bytes).into_string();
+        output_object.insert("oid".to_string(), Value::String(object_id));
+    }
 
     let parent_field: String = decoder.current_field.clone();
 
     loop {
-        if decoder.contents.get(decoder.index) == Some(&SpudTypes::ObjectEnd.as_u8())
+        let Some(&byte) = decoder.contents.get(decoder.index) else {
+            return Err(SpudError::DecodingError(
+                "unterminated object: the buffer ended before ObjectEnd".to_owned(),
+            ));
+        };
+
+        if byte == SpudTypes::ObjectEnd.as_u8()
             && decoder.contents.get(decoder.index + 1) == Some(&SpudTypes::ObjectEnd.as_u8())
         {
             break;
         }
 
-        let decoded_byte: Option<Value> = decoder.decode_byte(decoder.contents[decoder.index])?;
+        let decoded_byte: Option<Value> = decoder.decode_byte(byte)?;
 
-        if let Some(value) = decoded_byte {
-            output_object.insert(decoder.current_field.clone(), value);
+        if let Some(value) = decoded_byte
+            && !decoder.is_omitted(&value)
+        {
+            output_object.insert(decoder.output_field_name(), value);
         }
     }
 
@@ -60,6 +71,188 @@ mod tests {
         decoder.decode(false, false).unwrap();
     }
 
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_nested_field_order() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("zulu", 1u8)?;
+                obj.add_value("alpha", 2u8)?;
+                obj.add_value("mike", 3u8)?;
+                obj.object("nested", |nested: &SpudObjectSync| {
+                    nested.add_value("zulu", 1u8)?;
+                    nested.add_value("alpha", 2u8)?;
+                    nested.add_value("mike", 3u8)?;
+                    Ok(())
+                })?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let object: serde_json::Value = decoder.decode_values().unwrap().remove(0);
+
+        let top_level: Vec<&String> = object.as_object().unwrap().keys().collect();
+        let nested: Vec<&String> = object["nested"].as_object().unwrap().keys().collect();
+
+        assert_eq!(top_level, ["oid", "zulu", "alpha", "mike", "nested"]);
+        assert_eq!(nested, ["oid", "zulu", "alpha", "mike"]);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_object_without_oid() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("value", 1u8)?;
+                obj.object("nested", |nested: &SpudObjectSync| {
+                    nested.add_value("value", 2u8)?;
+                    Ok(())
+                })?;
+                obj.add_object_array("items", |array: &SpudObjectArraySync| {
+                    array.push_object(|item: &SpudObjectSync| {
+                        item.add_value("value", 3u8)?;
+                        Ok(())
+                    })?;
+                    Ok(())
+                })?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        decoder.with_include_oid(false);
+
+        assert_eq!(
+            decoder.decode_values().unwrap(),
+            [serde_json::json!({
+                "value": 1,
+                "nested": { "value": 2 },
+                "items": [{ "value": 3 }],
+            })]
+        );
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_field_rename() {
+        use std::collections::HashMap;
+
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("usr_nm", "Alice")?;
+                obj.add_value("age", 30u8)?;
+                obj.object("addr", |nested: &SpudObjectSync| {
+                    nested.add_value("usr_nm", "Bob")?;
+                    Ok(())
+                })?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        decoder
+            .with_include_oid(false)
+            .with_field_rename(HashMap::from([
+                ("usr_nm".to_owned(), "userName".to_owned()),
+                ("addr".to_owned(), "address".to_owned()),
+            ]));
+
+        assert_eq!(
+            decoder.decode_values().unwrap(),
+            [serde_json::json!({
+                "userName": "Alice",
+                "age": 30,
+                "address": { "userName": "Bob" },
+            })]
+        );
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_object_unterminated() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value("value", 1u8)?;
+                Ok(())
+            })
+            .unwrap();
+
+        let mut encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        // Drops both ObjectEnd bytes, keeping the trailer
+        let object_end: usize = encoded_bytes.len() - 6;
+
+        encoded_bytes.drain(object_end..object_end + 2);
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let Err(SpudError::DecodingError(message)) = decoder.decode(false, false) else {
+            panic!("expected a decoding error");
+        };
+
+        assert!(message.contains("unterminated object"));
+    }
+
+    #[test]
+    fn test_nested_object_unterminated() {
+        use indexmap::IndexMap;
+
+        use crate::{
+            spud_decoder::{DecoderObject, DecoderOptions},
+            spud_types::{SpudNumberTypes, SpudTypes},
+        };
+
+        let mut contents: Vec<u8> = vec![
+            SpudTypes::ObjectStart.as_u8(),
+            SpudTypes::ObjectStart.as_u8(),
+        ];
+
+        contents.extend_from_slice(&[0; 10]);
+        contents.extend_from_slice(&[
+            SpudTypes::FieldNameId.as_u8(),
+            2,
+            SpudTypes::ObjectStart.as_u8(),
+            SpudTypes::ObjectStart.as_u8(),
+        ]);
+        contents.extend_from_slice(&[0; 10]);
+        contents.extend_from_slice(&[
+            SpudTypes::FieldNameId.as_u8(),
+            3,
+            SpudTypes::Number(SpudNumberTypes::U8).as_u8(),
+            1,
+        ]);
+
+        let field_names: IndexMap<u8, String> =
+            IndexMap::from([(2, "object".to_owned()), (3, "value".to_owned())]);
+        let options: DecoderOptions = DecoderOptions::default();
+
+        let mut decoder: DecoderObject<'_> = DecoderObject::new(&contents, &field_names, &options);
+
+        let Err(SpudError::DecodingError(message)) = decoder.decode() else {
+            panic!("expected a decoding error");
+        };
+
+        assert!(message.contains("unterminated object"));
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn test_single_object_async() {
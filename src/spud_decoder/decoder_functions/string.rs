@@ -11,7 +11,7 @@ pub(crate) fn string(
     *next_steps = string_len;
 
     Ok(Value::String(String::from_utf8(
-        decoder.contents[decoder.index..decoder.index + string_len].to_vec(),
+        decoder.peek_bytes(string_len)?.to_vec(),
     )?))
 }
 
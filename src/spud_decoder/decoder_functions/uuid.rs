@@ -0,0 +1,76 @@
+use serde_json::Value;
+
+use crate::{SpudError, spud_decoder::DecoderObject, types::Uuid};
+
+pub(crate) fn uuid(decoder: &mut DecoderObject) -> Result<Value, SpudError> {
+    decoder.next(1)?;
+
+    let read_bytes: &[u8] = decoder.read_bytes(16)?;
+
+    let bytes: [u8; 16] = read_bytes
+        .try_into()
+        .map_err(|_| SpudError::DecodingError("Invalid Uuid bytes".to_owned()))?;
+
+    Ok(Value::String(Uuid::from_bytes(bytes).to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{types::Uuid, *};
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_uuid() {
+        use core::str::FromStr;
+
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj: &SpudObjectSync| {
+                obj.add_value(
+                    "uuid",
+                    Uuid::from_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap(),
+                )?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(output["uuid"], "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_uuid_async() {
+        use std::sync::Arc;
+
+        use tokio::sync::{Mutex, MutexGuard};
+
+        let builder: SpudBuilderAsync = SpudBuilderAsync::new();
+
+        builder
+            .object(async |obj: Arc<Mutex<SpudObjectAsync>>| {
+                let obj: MutexGuard<'_, SpudObjectAsync> = obj.lock().await;
+
+                obj.add_value("uuid", Uuid::from_bytes([0xAB; 16])).await?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().await.unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(output["uuid"], "abababab-abab-abab-abab-abababababab");
+    }
+}
@@ -0,0 +1,23 @@
+use std::collections::HashMap;
+
+use crate::spud_decoder::{BlobEncoding, NullRepr};
+
+/// Flags that control how decoded SPUD values are rendered as JSON.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Default, Debug, Clone)]
+pub(crate) struct DecoderOptions {
+    pub(crate) normalize_number_display: bool,
+    pub(crate) decimal_as_parts: bool,
+    pub(crate) decimal_as_number: bool,
+    pub(crate) annotate_types: bool,
+    pub(crate) blob_encoding: BlobEncoding,
+    pub(crate) coalesce_byte_arrays: bool,
+    pub(crate) shortest_f32: bool,
+    pub(crate) null_as: NullRepr,
+    /// Inverted so that the derived default keeps every object's `oid`.
+    pub(crate) omit_oid: bool,
+    /// Output names for fields, keyed by the name stored in the file.
+    pub(crate) field_renames: HashMap<String, String>,
+    /// Set from the file's `Capabilities::OBJECT_VERSION` flag rather than by the caller.
+    pub(crate) object_versions: bool,
+}
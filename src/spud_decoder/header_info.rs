@@ -0,0 +1,43 @@
+use indexmap::IndexMap;
+
+use crate::types::Capabilities;
+
+/// The header of a SPUD file, read by `SpudDecoder::read_header_only` without loading its objects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderInfo {
+    version: String,
+    field_names: IndexMap<u8, String>,
+    capabilities: Capabilities,
+}
+
+impl HeaderInfo {
+    pub(crate) fn new(
+        version: String,
+        field_names: IndexMap<u8, String>,
+        capabilities: Capabilities,
+    ) -> Self {
+        Self {
+            version,
+            field_names,
+            capabilities,
+        }
+    }
+
+    #[must_use]
+    /// Returns the version string the file starts with, e.g. `SPUD-0.8.2`.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    #[must_use]
+    /// Returns the field-name table, keyed by id.
+    pub fn field_names(&self) -> &IndexMap<u8, String> {
+        &self.field_names
+    }
+
+    #[must_use]
+    /// Returns the optional format features the file declares in its trailer.
+    pub fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+}
@@ -0,0 +1,53 @@
+use crate::SPUD_VERSION;
+
+/// Checks whether a byte buffer starts like a SPUD file, without parsing it.
+///
+/// Only the `SPUD-` prefix of the version string is compared, so files written by other
+/// versions of the format are recognised too.
+///
+/// # Examples
+///
+/// ```rust
+/// use spud_rs::is_spud;
+///
+/// assert!(is_spud(b"SPUD-0.8.2\x01\xDE\xAD\xBE\xEF"));
+/// assert!(!is_spud(br#"{"name": "Alice"}"#));
+/// ```
+#[must_use]
+pub fn is_spud(bytes: &[u8]) -> bool {
+    let magic: &str = SPUD_VERSION
+        .find('-')
+        .map_or(SPUD_VERSION, |index| &SPUD_VERSION[..=index]);
+
+    bytes.starts_with(magic.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_is_spud_valid() {
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("name", "Alice")?;
+                Ok(())
+            })
+            .unwrap();
+
+        assert!(is_spud(&builder.encode().unwrap()));
+    }
+
+    #[test]
+    fn test_is_spud_json() {
+        assert!(!is_spud(br#"{"name": "Alice"}"#));
+    }
+
+    #[test]
+    fn test_is_spud_empty() {
+        assert!(!is_spud(&[]));
+    }
+}
@@ -1,16 +1,1751 @@
 mod decode_object;
 mod decoder_functions;
+mod decoder_options;
 
 pub(crate) use decode_object::DecoderObject;
+pub(crate) use decoder_options::DecoderOptions;
 
+mod blob_encoding;
+#[cfg(feature = "cbor")]
+mod cbor;
 mod decoder;
+#[cfg(feature = "sync")]
+mod header_info;
+mod is_spud;
+mod null_repr;
+mod object_iter;
+mod patch_field;
+mod range;
 
+pub use blob_encoding::BlobEncoding;
 pub use decoder::SpudDecoder;
+#[cfg(feature = "sync")]
+pub use header_info::HeaderInfo;
+pub use is_spud::is_spud;
+pub use null_repr::NullRepr;
+pub use object_iter::SpudObjectIter;
+pub use patch_field::patch_field;
+pub use range::{range_from_value, range_inclusive_from_value};
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_time_range() {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        use crate::SpudBuilderSync;
+
+        let before: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        for i in 0..3_u8 {
+            builder
+                .object(|obj| {
+                    obj.add_value("index", i)?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let after: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        assert_eq!(decoder.object_ids().unwrap().len(), 3);
+
+        let (min, max): (u32, u32) = decoder.time_range().unwrap();
+
+        assert!(min <= max);
+        assert!(before <= u64::from(min));
+        assert!(u64::from(max) <= after);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_by_id() {
+        use indexmap::IndexMap;
+        use serde_json::Value;
+
+        use crate::{SpudBuilderSync, types::ObjectId};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        for i in 0..3_u8 {
+            builder
+                .object(|obj| {
+                    obj.add_value("index", i)?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let object_ids: Vec<ObjectId> = decoder.object_ids().unwrap();
+        let objects: IndexMap<ObjectId, Value> = decoder.decode_by_id().unwrap();
+
+        assert_eq!(objects.len(), 3);
+
+        let second: &Value = &objects[&object_ids[1]];
+
+        assert_eq!(second["index"], 1);
+        assert_eq!(second["oid"], object_ids[1].to_string());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_values_repeated() {
+        use serde_json::Value;
+
+        use crate::SpudBuilderSync;
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        for i in 0..2_u8 {
+            builder
+                .object(|obj| {
+                    obj.add_value("index", i)?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let first: Vec<Value> = decoder.decode_values().unwrap();
+        let second: Vec<Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[1]["index"], 1);
+        assert_eq!(first, second);
+    }
+
+    #[cfg(feature = "sync")]
+    fn validation_schema() -> crate::SpudSchema {
+        use crate::{SpudSchema, SpudSchemaTypes};
+
+        let mut schema: SpudSchema = SpudSchema::new();
+
+        schema
+            .add_field("name", SpudSchemaTypes::String)
+            .add_field("age", SpudSchemaTypes::Number)
+            .add_field(
+                "nickname",
+                SpudSchemaTypes::Optional(Box::new(SpudSchemaTypes::String)),
+            );
+
+        schema
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_validated_conforming() {
+        use crate::{SpudBuilderSync, types::SpudString};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("name", SpudString::from("Alice"))?;
+                obj.add_value("age", 30u8)?;
+                obj.add_value("extra", true)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode_validated(&validation_schema()).unwrap()).unwrap();
+
+        assert_eq!(output["name"], "Alice");
+        assert_eq!(output["age"], 30);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_validated_missing_required() {
+        use crate::{SpudBuilderSync, SpudError, types::SpudString};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("name", SpudString::from("Alice"))?;
+                obj.add_value("age", 30u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        builder
+            .object(|obj| {
+                obj.add_value("name", SpudString::from("Bob"))?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let Err(SpudError::ValidationError(message)) =
+            decoder.decode_validated(&validation_schema())
+        else {
+            panic!("expected a validation error");
+        };
+
+        assert_eq!(message, "object 1: missing required field 'age'");
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_validated_wrong_type() {
+        use crate::{SpudBuilderSync, SpudError, types::SpudString};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("name", SpudString::from("Alice"))?;
+                obj.add_value("age", 30u8)?;
+                obj.add_value("nickname", 7u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let Err(SpudError::ValidationError(message)) =
+            decoder.decode_validated(&validation_schema())
+        else {
+            panic!("expected a validation error");
+        };
+
+        assert_eq!(
+            message,
+            "object 0: field 'nickname' expected Optional<String>, found Number"
+        );
+    }
+
+    #[cfg(feature = "sync")]
+    fn nested_schema() -> crate::SpudSchema {
+        use crate::{SpudSchema, SpudSchemaTypes};
+
+        let mut address: SpudSchema = SpudSchema::new();
+
+        address
+            .add_field("city", SpudSchemaTypes::String)
+            .add_field("zip", SpudSchemaTypes::Number);
+
+        let mut schema: SpudSchema = SpudSchema::new();
+
+        schema
+            .add_field("born", SpudSchemaTypes::Date)
+            .add_field(
+                "tags",
+                SpudSchemaTypes::Array(Box::new(SpudSchemaTypes::String)),
+            )
+            .add_field("address", SpudSchemaTypes::Object(address));
+
+        schema
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_validated_nested() {
+        use crate::{SpudBuilderSync, types::Date};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("born", Date::new(1990, 4, 12)?)?;
+                obj.add_value("tags", vec!["admin", "staff"])?;
+                obj.object("address", |address| {
+                    address.add_value("city", "Utrecht")?;
+                    address.add_value("zip", 3511u16)?;
+
+                    Ok(())
+                })?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode_validated(&nested_schema()).unwrap()).unwrap();
+
+        assert_eq!(output["address"]["city"], "Utrecht");
+        assert_eq!(output["tags"], serde_json::json!(["admin", "staff"]));
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_validated_nested_violations() {
+        use crate::{SpudBuilderSync, SpudError, types::Date};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("born", Date::new(1990, 4, 12)?)?;
+                obj.add_value("tags", vec!["admin"])?;
+                obj.object("address", |address| {
+                    address.add_value("city", "Utrecht")?;
+                    address.add_value("zip", "3511")?;
+
+                    Ok(())
+                })?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let Err(SpudError::ValidationError(message)) = decoder.decode_validated(&nested_schema())
+        else {
+            panic!("expected a validation error");
+        };
+
+        assert_eq!(
+            message,
+            "object 0: field 'address.zip' expected Number, found String"
+        );
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("born", Date::new(1990, 4, 12)?)?;
+                obj.add_value("tags", vec![1u8, 2u8])?;
+                obj.object("address", |address| {
+                    address.add_value("city", "Utrecht")?;
+                    address.add_value("zip", 3511u16)?;
+
+                    Ok(())
+                })?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let Err(SpudError::ValidationError(message)) = decoder.decode_validated(&nested_schema())
+        else {
+            panic!("expected a validation error");
+        };
+
+        assert_eq!(
+            message,
+            "object 0: field 'tags[0]' expected String, found Number"
+        );
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_capabilities() {
+        use crate::{SpudBuilderSync, SpudError, types::Capabilities};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("value", 1u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let mut encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        assert_eq!(
+            SpudDecoder::new(&encoded_bytes).unwrap().capabilities(),
+            Capabilities::empty()
+        );
+
+        let trailer_start: usize = encoded_bytes.len() - 4;
+
+        encoded_bytes[trailer_start..]
+            .copy_from_slice(&(Capabilities::INTERNING | Capabilities::METADATA).as_trailer());
+
+        let Err(SpudError::InvalidSpudFile(message)) = SpudDecoder::new(&encoded_bytes) else {
+            panic!("expected reserved capabilities to be rejected");
+        };
+
+        assert!(message.contains(r#"{"INTERNING", "METADATA"}"#));
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_object_checksums() {
+        use crate::{SpudBuilderSync, SpudError, types::SpudString};
+
+        let mut builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder.with_object_checksums();
+
+        for name in ["first", "second", "third"] {
+            builder
+                .object(|obj| {
+                    obj.add_value("name", SpudString::from(name))?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let mut encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        assert_eq!(
+            SpudDecoder::new(&encoded_bytes)
+                .unwrap()
+                .decode_to_value(true)
+                .unwrap()
+                .as_array()
+                .map(Vec::len),
+            Some(3)
+        );
+
+        let corrupted_at: usize = encoded_bytes
+            .windows(6)
+            .position(|window| window == b"second")
+            .unwrap();
+
+        encoded_bytes[corrupted_at] = b'S';
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<Result<serde_json::Value, SpudError>> = decoder.decode_checked().unwrap();
+
+        assert_eq!(objects.len(), 3);
+        assert_eq!(objects[0].as_ref().unwrap()["name"], "first");
+        assert!(
+            matches!(&objects[1], Err(SpudError::DecodingError(message)) if message.contains("checksum mismatch"))
+        );
+        assert_eq!(objects[2].as_ref().unwrap()["name"], "third");
+
+        assert!(decoder.decode_to_value(true).is_err());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_into_string_reuses_buffers() {
+        use indexmap::IndexMap;
+
+        use crate::SpudBuilderSync;
+
+        let encode = |names: &[&str]| -> Vec<u8> {
+            let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+            for name in names {
+                builder
+                    .object(|obj| {
+                        obj.add_value("name", *name)?;
+
+                        Ok(())
+                    })
+                    .unwrap();
+            }
+
+            builder.encode().unwrap()
+        };
+
+        let first: Vec<u8> = encode(&["Alice", "Bob"]);
+        let second: Vec<u8> = encode(&["Carol"]);
+
+        let mut json: String = String::new();
+        let mut objects: Vec<IndexMap<String, serde_json::Value>> = Vec::new();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&first).unwrap();
+
+        decoder.decode_into_string(&mut json, false, false).unwrap();
+        decoder.decode_values_into(&mut objects).unwrap();
+
+        let output: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(output[1]["name"], "Bob");
+        assert_eq!(objects.len(), 2);
+
+        let json_buffer: *const u8 = json.as_ptr();
+        let objects_buffer: *const IndexMap<String, serde_json::Value> = objects.as_ptr();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&second).unwrap();
+
+        decoder.decode_into_string(&mut json, false, false).unwrap();
+        decoder.decode_values_into(&mut objects).unwrap();
+
+        let output: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(output["name"], "Carol");
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0]["name"], "Carol");
+
+        assert_eq!(json.as_ptr(), json_buffer);
+        assert_eq!(objects.as_ptr(), objects_buffer);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_object_versions() {
+        use crate::{OBJECT_FORMAT_VERSION, SpudBuilderSync, SpudError, types::Capabilities};
+
+        let mut builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .with_object_versions()
+            .with_auto_timestamp("created_at");
+
+        builder
+            .object(|obj| {
+                obj.add_value("name", "Alice")?;
+                obj.object("address", |address| {
+                    address.add_value("city", "Utrecht")?;
+
+                    Ok(())
+                })?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let mut encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        assert!(
+            decoder
+                .capabilities()
+                .contains(Capabilities::OBJECT_VERSION)
+        );
+
+        let object: serde_json::Value = decoder.decode_values().unwrap().remove(0);
+
+        assert_eq!(object["name"], "Alice");
+        assert_eq!(object["address"]["city"], "Utrecht");
+        assert!(object["created_at"].is_string());
+
+        let oid: [u8; 10] = *decoder.object_ids().unwrap()[0].as_bytes();
+
+        let version_at: usize = encoded_bytes
+            .windows(10)
+            .position(|window| window == oid)
+            .unwrap()
+            + 10;
+
+        assert_eq!(encoded_bytes[version_at], OBJECT_FORMAT_VERSION);
+
+        encoded_bytes[version_at] = OBJECT_FORMAT_VERSION + 1;
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let Err(SpudError::DecodingError(message)) = decoder.decode_values() else {
+            panic!("expected a decoding error");
+        };
+
+        assert!(message.contains("unsupported object format version 2"));
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_structurally_equal() {
+        use crate::{SpudBuilderSync, types::SpudString};
+
+        let first: SpudBuilderSync = SpudBuilderSync::new();
+
+        first
+            .object(|obj| {
+                obj.add_value("name", SpudString::from("Alice"))?;
+                obj.object("address", |address| {
+                    address.add_value("city", SpudString::from("Rome"))?;
+                    address.add_value("zip", 100u32)?;
+
+                    Ok(())
+                })?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let second: SpudBuilderSync = SpudBuilderSync::new();
+
+        second
+            .object(|obj| {
+                obj.object("address", |address| {
+                    address.add_value("zip", 100u32)?;
+                    address.add_value("city", SpudString::from("Rome"))?;
+
+                    Ok(())
+                })?;
+                obj.add_value("name", SpudString::from("Alice"))?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let third: SpudBuilderSync = SpudBuilderSync::new();
+
+        third
+            .object(|obj| {
+                obj.add_value("name", SpudString::from("Bob"))?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let first_bytes: Vec<u8> = first.encode().unwrap();
+        let second_bytes: Vec<u8> = second.encode().unwrap();
+        let third_bytes: Vec<u8> = third.encode().unwrap();
+
+        let first_decoder: SpudDecoder = SpudDecoder::new(&first_bytes).unwrap();
+        let second_decoder: SpudDecoder = SpudDecoder::new(&second_bytes).unwrap();
+        let third_decoder: SpudDecoder = SpudDecoder::new(&third_bytes).unwrap();
+
+        assert_ne!(first_bytes, second_bytes);
+        assert!(first_decoder.structurally_equal(&second_decoder).unwrap());
+        assert!(!first_decoder.structurally_equal(&third_decoder).unwrap());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_semantic_eq() {
+        use crate::{SpudBuilderSync, types::ObjectId};
+
+        let encode = |oid: ObjectId, name: &str| {
+            let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+            builder
+                .object_with_id(oid, |obj| {
+                    obj.add_value("name", name)?;
+                    obj.add_value("age", 30u8)?;
+
+                    Ok(())
+                })
+                .unwrap();
+
+            builder.encode().unwrap()
+        };
+
+        let oid: ObjectId = ObjectId::from([1; 10]);
+        let other_oid: ObjectId = ObjectId::from([2; 10]);
+
+        let first: Vec<u8> = encode(oid, "Alice");
+        let same_oid: Vec<u8> = encode(oid, "Alice");
+        let new_oid: Vec<u8> = encode(other_oid, "Alice");
+        let different: Vec<u8> = encode(oid, "Bob");
+
+        assert!(SpudDecoder::semantic_eq(&first, &same_oid, false).unwrap());
+        assert!(!SpudDecoder::semantic_eq(&first, &new_oid, false).unwrap());
+        assert!(SpudDecoder::semantic_eq(&first, &new_oid, true).unwrap());
+        assert!(!SpudDecoder::semantic_eq(&first, &different, true).unwrap());
+
+        assert!(SpudDecoder::semantic_eq(&first, &[0xFF], true).is_err());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_empty_document() {
+        use crate::{SPUD_VERSION, SpudBuilderSync, SpudError, spud_types::SpudTypes};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut expected: Vec<u8> = SPUD_VERSION.as_bytes().to_vec();
+
+        expected.push(SpudTypes::FieldNameListEnd.as_u8());
+        expected.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+
+        assert_eq!(encoded_bytes, expected);
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        assert_eq!(decoder.decode(false, false).unwrap(), "[]");
+        assert_eq!(decoder.decode(false, true).unwrap(), "[]");
+        assert!(decoder.decode_values().unwrap().is_empty());
+        assert!(decoder.object_ids().unwrap().is_empty());
+        assert_eq!(decoder.objects().count(), 0);
+
+        assert!(matches!(
+            decoder.decode_single(),
+            Err(SpudError::DecodingError(message)) if message == "Expected exactly one object, found 0"
+        ));
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_single() {
+        use indexmap::IndexMap;
+
+        use crate::{SpudBuilderSync, SpudError};
+
+        let build = |count: u8| -> Vec<u8> {
+            let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+            for i in 0..count {
+                builder
+                    .object(|obj| {
+                        obj.add_value("index", i)?;
+
+                        Ok(())
+                    })
+                    .unwrap();
+            }
+
+            builder.encode().unwrap()
+        };
+
+        let object: IndexMap<String, serde_json::Value> = SpudDecoder::new(&build(1))
+            .unwrap()
+            .decode_single()
+            .unwrap();
+
+        assert_eq!(object["index"], 0);
+
+        assert!(matches!(
+            SpudDecoder::new(&build(0)).unwrap().decode_single(),
+            Err(SpudError::DecodingError(message)) if message.contains("found 0")
+        ));
+        assert!(matches!(
+            SpudDecoder::new(&build(2)).unwrap().decode_single(),
+            Err(SpudError::DecodingError(message)) if message.contains("found 2")
+        ));
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_fingerprint() {
+        use crate::{SpudBuilderSync, types::SpudString};
+
+        let build = |city: &str, reversed: bool| -> Vec<u8> {
+            let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+            builder
+                .object(|obj| {
+                    if reversed {
+                        obj.add_value("zip", 100u32)?;
+                        obj.add_value("city", SpudString::from(city))?;
+                    } else {
+                        obj.add_value("city", SpudString::from(city))?;
+                        obj.add_value("zip", 100u32)?;
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+
+            builder.encode().unwrap()
+        };
+
+        let first: [u8; 32] = SpudDecoder::new(&build("Rome", false))
+            .unwrap()
+            .fingerprint()
+            .unwrap();
+        let reordered: [u8; 32] = SpudDecoder::new(&build("Rome", true))
+            .unwrap()
+            .fingerprint()
+            .unwrap();
+        let changed: [u8; 32] = SpudDecoder::new(&build("Oslo", false))
+            .unwrap()
+            .fingerprint()
+            .unwrap();
+
+        assert_eq!(first, reordered);
+        assert_ne!(first, changed);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_from_vec() {
+        use crate::{SpudBuilderSync, types::SpudString};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("name", SpudString::from("Alice"))?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut borrowed: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+        let mut owned: SpudDecoder = SpudDecoder::from_vec(encoded_bytes).unwrap();
+
+        assert_eq!(
+            borrowed.decode(false, false).unwrap(),
+            owned.decode(false, false).unwrap()
+        );
+
+        assert!(SpudDecoder::from_vec(b"SPUD".to_vec()).is_err());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_new_from_reader() {
+        use std::io::Cursor;
+
+        use crate::{SpudBuilderSync, types::SpudString};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        for name in ["Alice", "Bob"] {
+            builder
+                .object(|obj| {
+                    obj.add_value("name", SpudString::from(name))?;
+                    obj.add_value("age", 30u8)?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut from_slice: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+        let mut from_reader: SpudDecoder =
+            SpudDecoder::new_from_reader(Cursor::new(&encoded_bytes)).unwrap();
+
+        assert_eq!(from_reader.field_names, from_slice.field_names);
+        assert_eq!(
+            from_reader.decode(false, false).unwrap(),
+            from_slice.decode(false, false).unwrap()
+        );
+
+        let offsets: Vec<usize> = from_reader
+            .objects()
+            .map(|object| object.unwrap().0)
+            .collect();
+
+        assert_eq!(
+            offsets,
+            from_slice
+                .objects()
+                .map(|object| object.unwrap().0)
+                .collect::<Vec<usize>>()
+        );
+
+        assert!(SpudDecoder::new_from_reader(Cursor::new(b"SPUD")).is_err());
+        assert!(SpudDecoder::new_from_reader(Cursor::new(&encoded_bytes[..14])).is_err());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_read_header_only() {
+        use std::{
+            fs,
+            io::{Cursor, Read, Seek, SeekFrom},
+        };
+
+        use crate::{SPUD_VERSION, SpudBuilderSync, types::BinaryBlob};
+
+        struct CountingReader<'a> {
+            inner: Cursor<&'a [u8]>,
+            bytes_read: usize,
+        }
+
+        impl Read for CountingReader<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let read: usize = self.inner.read(buf)?;
+
+                self.bytes_read += read;
+
+                Ok(read)
+            }
+        }
+
+        impl Seek for CountingReader<'_> {
+            fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+                self.inner.seek(pos)
+            }
+        }
+
+        let mut builder: SpudBuilderSync = SpudBuilderSync::new();
+        let blob: Vec<u8> = vec![7; 1024 * 1024];
+
+        for _ in 0..4 {
+            builder
+                .object(|obj| {
+                    obj.add_value("name", "Alice")?;
+                    obj.add_value("payload", BinaryBlob::new(&blob))?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        builder.encode().unwrap();
+        builder.build_file("./.tmp/spud", "header_only").unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.data.lock().unwrap().clone();
+
+        let header: HeaderInfo =
+            SpudDecoder::read_header_only("./.tmp/spud/header_only.spud").unwrap();
+
+        assert_eq!(header.version(), SPUD_VERSION);
+        assert_eq!(
+            header.field_names(),
+            &SpudDecoder::new(&encoded_bytes).unwrap().field_names
+        );
+        assert!(header.capabilities().is_empty());
+
+        let mut reader: CountingReader<'_> = CountingReader {
+            inner: Cursor::new(&encoded_bytes),
+            bytes_read: 0,
+        };
+
+        assert_eq!(SpudDecoder::read_header_info(&mut reader).unwrap(), header);
+        assert!(reader.bytes_read < 64);
+
+        fs::remove_file("./.tmp/spud/header_only.spud").unwrap();
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_truncated_file_rejected() {
+        use crate::{SpudBuilderSync, SpudError};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("value", 1u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        assert!(!decoder.file_contents.ends_with(&[0xDE, 0xAD, 0xBE, 0xEF]));
+
+        for truncated_len in [encoded_bytes.len() - 1, encoded_bytes.len() - 4] {
+            let Err(SpudError::InvalidSpudFile(_)) =
+                SpudDecoder::new(&encoded_bytes[..truncated_len])
+            else {
+                panic!("expected an invalid file error");
+            };
+        }
+
+        let header_len: usize = encoded_bytes.len() - decoder.file_contents.len() - 4;
+
+        let Err(SpudError::InvalidSpudFile(_)) =
+            SpudDecoder::from_vec(encoded_bytes[..header_len + 2].to_vec())
+        else {
+            panic!("expected an invalid file error");
+        };
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_scan_payload_ending_in_object_end() {
+        use crate::{SpudBuilderSync, spud_types::SpudTypes};
+
+        let end: u8 = SpudTypes::ObjectEnd.as_u8();
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.object("inner", |inner| {
+                    inner.add_value("code", u16::from_le_bytes([end, end]))?;
+
+                    Ok(())
+                })?;
+                obj.add_value("last", end)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        builder
+            .object(|obj| {
+                obj.add_value("last", end)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let objects: Vec<serde_json::Value> = SpudDecoder::new(&encoded_bytes)
+            .unwrap()
+            .decode_values()
+            .unwrap();
+
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0]["inner"]["code"], u16::from_le_bytes([end, end]));
+        assert_eq!(objects[0]["last"], end);
+        assert_eq!(objects[1]["last"], end);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_scan_object_id_containing_object_end() {
+        use crate::{SpudBuilderSync, spud_types::SpudTypes};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        for value in 0..2u8 {
+            builder
+                .object(|obj| {
+                    obj.add_value("value", value)?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let mut encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let start: u8 = SpudTypes::ObjectStart.as_u8();
+        let end: u8 = SpudTypes::ObjectEnd.as_u8();
+
+        // Object ids are random, so write a doubled ObjectEnd into the first one
+        let oid_start: usize = encoded_bytes
+            .windows(2)
+            .position(|window| window == [start, start])
+            .unwrap()
+            + 2;
+
+        encoded_bytes[oid_start..oid_start + 10].fill(end);
+
+        let objects: Vec<serde_json::Value> = SpudDecoder::new(&encoded_bytes)
+            .unwrap()
+            .decode_values()
+            .unwrap();
+
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0]["value"], 0);
+        assert_eq!(objects[1]["value"], 1);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_footer_field_table() {
+        use std::io::Cursor;
+
+        use crate::{
+            SPUD_VERSION, SpudBuilderSync, SpudError, spud_types::SpudTypes, types::Capabilities,
+        };
+
+        let build = |footer: bool| -> SpudBuilderSync {
+            let mut builder: SpudBuilderSync = SpudBuilderSync::new();
+
+            if footer {
+                builder.with_footer_field_table().with_object_checksums();
+            }
+
+            for (name, age) in [("Alice", 30u8), ("Bob", 41u8)] {
+                builder
+                    .object(|obj| {
+                        obj.add_value("name", name)?;
+                        obj.object("stats", |stats| {
+                            stats.add_value("age", age)?;
+
+                            Ok(())
+                        })?;
+
+                        Ok(())
+                    })
+                    .unwrap();
+            }
+
+            builder
+        };
+
+        let header_table: SpudDecoder = SpudDecoder::new(&build(false).encode().unwrap()).unwrap();
+
+        let expected: Vec<serde_json::Value> = header_table.decode_values().unwrap();
+
+        let footer_builder: SpudBuilderSync = build(true);
+
+        let mut streamed: Vec<u8> = Vec::new();
+
+        footer_builder.encode_to_writer(&mut streamed).unwrap();
+
+        let encoded_bytes: Vec<u8> = footer_builder.encode().unwrap();
+
+        assert_eq!(streamed, encoded_bytes);
+        assert_eq!(
+            encoded_bytes[SPUD_VERSION.len()],
+            SpudTypes::FieldNameListEnd.as_u8()
+        );
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        assert!(
+            decoder
+                .capabilities()
+                .contains(Capabilities::FOOTER_FIELD_TABLE)
+        );
+        assert!(
+            decoder
+                .field_names
+                .values()
+                .eq(header_table.field_names.values())
+        );
+
+        let mut objects: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        for (object, expected) in objects.iter_mut().zip(&expected) {
+            object["oid"] = expected["oid"].clone();
+            object["stats"]["oid"] = expected["stats"]["oid"].clone();
+        }
+
+        assert_eq!(objects, expected);
+
+        let header: HeaderInfo =
+            SpudDecoder::read_header_info(&mut Cursor::new(&encoded_bytes)).unwrap();
+
+        assert_eq!(header.field_names(), &decoder.field_names);
+
+        let extracted: SpudDecoder = SpudDecoder::new(&decoder.extract_object(1).unwrap()).unwrap();
+
+        assert!(
+            !extracted
+                .capabilities()
+                .contains(Capabilities::FOOTER_FIELD_TABLE)
+        );
+        assert_eq!(extracted.decode_values().unwrap()[0]["name"], "Bob");
+
+        let mut corrupted: Vec<u8> = encoded_bytes.clone();
+        let offset_at: usize = corrupted.len() - 12;
+
+        corrupted[offset_at] ^= 0xFF;
+
+        assert!(matches!(
+            SpudDecoder::new(&corrupted),
+            Err(SpudError::InvalidSpudFile(_))
+        ));
+    }
+
+    #[test]
+    fn test_scan_depth_limit() {
+        use crate::{SPUD_VERSION, SpudError, spud_types::SpudTypes};
+
+        let mut file: Vec<u8> = SPUD_VERSION.as_bytes().to_vec();
+
+        file.push(SpudTypes::FieldNameListEnd.as_u8());
+
+        for _ in 0..2000 {
+            file.extend_from_slice(&[
+                SpudTypes::ObjectStart.as_u8(),
+                SpudTypes::ObjectStart.as_u8(),
+            ]);
+            file.extend_from_slice(&[0; 10]);
+        }
+
+        file.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+
+        let decoder: SpudDecoder = SpudDecoder::from_vec(file).unwrap();
+
+        let Err(SpudError::DecodingError(message)) = decoder.decode_values() else {
+            panic!("expected a decoding error");
+        };
+
+        assert_eq!(
+            message,
+            "object starting at byte 0 is nested deeper than 1024 levels"
+        );
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_to_value() {
+        use crate::SpudBuilderSync;
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("value", 0.1f64)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let single: serde_json::Value = decoder.decode_to_value(false).unwrap();
+        let wrapped: serde_json::Value = decoder.decode_to_value(true).unwrap();
+
+        assert_eq!(single["value"], 0.1);
+        assert_eq!(wrapped, serde_json::Value::Array(vec![single.clone()]));
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(parsed, single);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_objects_offsets() {
+        use indexmap::IndexMap;
+
+        use crate::{SpudBuilderSync, spud_types::SpudTypes};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        for i in 0..3_u8 {
+            builder
+                .object(|obj| {
+                    obj.add_value("index", i)?;
+                    obj.object("nested", |nested| {
+                        nested.add_value("index", i)?;
+
+                        Ok(())
+                    })?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<(usize, IndexMap<String, serde_json::Value>)> =
+            decoder.objects().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(objects.len(), 3);
+
+        for (index, (offset, object)) in objects.iter().enumerate() {
+            assert_eq!(
+                encoded_bytes[*offset..*offset + 2],
+                [
+                    SpudTypes::ObjectStart.as_u8(),
+                    SpudTypes::ObjectStart.as_u8()
+                ]
+            );
+            assert_eq!(object["index"], index);
+        }
+
+        assert!(objects.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_object_count() {
+        use crate::SpudBuilderSync;
+
+        let mut builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder.with_object_checksums();
+
+        assert_eq!(
+            SpudDecoder::new(&SpudBuilderSync::new().encode().unwrap())
+                .unwrap()
+                .object_count()
+                .unwrap(),
+            0
+        );
+
+        for i in 0..4_u8 {
+            builder
+                .object(|obj| {
+                    obj.object("nested", |nested| {
+                        nested.add_value("index", i)?;
+
+                        Ok(())
+                    })?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        assert_eq!(decoder.object_count().unwrap(), 4);
+        assert_eq!(decoder.objects().count(), 4);
+    }
+
+    #[cfg(all(feature = "sync", feature = "cbor"))]
+    #[test]
+    fn test_build_cbor() {
+        use crate::{SpudBuilderSync, types::BinaryBlob};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("blob", BinaryBlob::new(&[1, 2]))?;
+                obj.add_value("half", 0.5_f32)?;
+                obj.add_value("big", u128::MAX)?;
+                obj.add_value("small", -2_i8)?;
+                obj.add_value("list", vec![true, false])?;
+                obj.add_value("none", ())?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let cbor: Vec<u8> = SpudDecoder::new(&encoded_bytes)
+            .unwrap()
+            .build_cbor()
+            .unwrap();
+
+        let oid: String = SpudDecoder::new(&encoded_bytes)
+            .unwrap()
+            .object_ids()
+            .unwrap()[0]
+            .to_string();
+
+        let mut expected: Vec<u8> = vec![
+            0x81,
+            0xA7,
+            0x63,
+            b'o',
+            b'i',
+            b'd',
+            0x60 + u8::try_from(oid.len()).unwrap(),
+        ];
+
+        expected.extend_from_slice(oid.as_bytes());
+        expected.extend_from_slice(&[0x64, b'b', b'l', b'o', b'b', 0x42, 1, 2]);
+        expected.extend_from_slice(&[0x64, b'h', b'a', b'l', b'f', 0xFA, 0x3F, 0x00, 0x00, 0x00]);
+        expected.extend_from_slice(&[0x63, b'b', b'i', b'g', 0xC2, 0x50]);
+        expected.extend_from_slice(&[0xFF; 16]);
+        expected.extend_from_slice(&[0x65, b's', b'm', b'a', b'l', b'l', 0x21]);
+        expected.extend_from_slice(&[0x64, b'l', b'i', b's', b't', 0x82, 0xF5, 0xF4]);
+        expected.extend_from_slice(&[0x64, b'n', b'o', b'n', b'e', 0xF6]);
+
+        assert_eq!(cbor, expected);
+    }
+
+    #[cfg(all(feature = "sync", feature = "serde"))]
+    #[test]
+    fn test_decode_into() {
+        use indexmap::IndexMap;
+
+        use crate::SpudBuilderSync;
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        for i in 0..2_u8 {
+            builder
+                .object(|obj| {
+                    obj.add_value("index", i)?;
+                    obj.add_value("name", "Alice")?;
+
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let objects: Vec<IndexMap<String, serde_json::Value>> = decoder.decode_into().unwrap();
+
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[1]["index"], 1);
+        assert_eq!(objects[1]["name"], "Alice");
+        assert!(objects[1]["oid"].is_string());
+    }
+
+    #[cfg(all(feature = "sync", feature = "serde"))]
+    #[test]
+    fn test_decode_into_shape_mismatch() {
+        use indexmap::IndexMap;
+
+        use crate::{SpudBuilderSync, SpudError};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("index", 1u8)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let single: IndexMap<String, serde_json::Value> = decoder.decode_into().unwrap();
+
+        assert_eq!(single["index"], 1);
+
+        let Err(SpudError::DecodingError(_)) = decoder.decode_into::<Vec<u8>>() else {
+            panic!("expected a decoding error");
+        };
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_coalesce_byte_arrays() {
+        use crate::{SpudBuilderSync, types::BinaryBlob};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("bytes", &b"foobar"[..])?;
+                obj.add_value("numbers", vec![1u16, 2, 3])?;
+                obj.add_value("blob", BinaryBlob::new(b"foo"))?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(
+            output["bytes"],
+            serde_json::json!([102, 111, 111, 98, 97, 114])
+        );
+
+        decoder
+            .with_blob_encoding(BlobEncoding::Base64)
+            .with_coalesce_byte_arrays(true);
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(output["bytes"], "Zm9vYmFy");
+        assert_eq!(output["numbers"], serde_json::json!([1, 2, 3]));
+        assert_eq!(output["blob"], "Zm9v");
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_annotate_types() {
+        use crate::{
+            SpudBuilderSync,
+            types::{Date, SpudString},
+        };
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("age", 30u8)?;
+                obj.add_value("name", SpudString::from("Alice"))?;
+                obj.add_value("born", Date::new(2023, 3, 15)?)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        decoder.with_annotate_types(true);
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, false).unwrap()).unwrap();
+
+        assert_eq!(
+            output["age"],
+            serde_json::json!({"type": "U8", "value": 30})
+        );
+        assert_eq!(
+            output["name"],
+            serde_json::json!({"type": "String", "value": "Alice"})
+        );
+        assert_eq!(
+            output["born"],
+            serde_json::json!({"type": "Date", "value": "2023-03-15"})
+        );
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_extract_object() {
+        use serde_json::Value;
+
+        use crate::{SpudBuilderSync, types::SpudString};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("only_first", true)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        builder
+            .object(|obj| {
+                obj.add_value("name", SpudString::from("Bob"))?;
+                obj.object("address", |address| {
+                    address.add_value("city", SpudString::from("Rome"))?;
+                    address.add_enum("kind", "home", &["home", "work"])?;
+
+                    Ok(())
+                })?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        builder
+            .object(|obj| {
+                obj.add_value("name", SpudString::from("Carol"))?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let extracted_bytes: Vec<u8> = decoder.extract_object(1).unwrap();
+
+        let extracted: SpudDecoder = SpudDecoder::new(&extracted_bytes).unwrap();
+
+        let extracted_values: Vec<Value> = extracted.decode_values().unwrap();
+
+        assert_eq!(extracted_values.len(), 1);
+        assert_eq!(extracted_values[0], decoder.decode_values().unwrap()[1]);
+
+        let mut header_names: Vec<&str> =
+            extracted.field_names.values().map(String::as_str).collect();
+
+        header_names.sort_unstable();
+
+        assert_eq!(header_names, ["address", "city", "home", "kind", "name"]);
+
+        assert!(decoder.extract_object(3).is_err());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_objects_flattened_ndjson() {
+        use crate::{SpudBuilderSync, types::SpudString};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("email", SpudString::from("alice@example.com"))?;
+
+                obj.object("userdata", |inner| {
+                    inner.add_value("name", SpudString::from("Alice"))?;
+
+                    Ok(())
+                })?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: String = decoder.decode_objects_flattened_ndjson().unwrap();
+
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+
+        assert_eq!(lines[0]["_path"], "[0]");
+        assert_eq!(lines[0]["email"], "alice@example.com");
+        assert!(lines[0].get("userdata").is_none());
+
+        assert_eq!(lines[1]["_path"], "[0].userdata");
+        assert_eq!(lines[1]["name"], "Alice");
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_objects_flattened_ndjson_mixed_array() {
+        use crate::{
+            SpudBuilderSync,
+            spud_types::{SpudNumberTypes, SpudTypes},
+        };
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        let name_id: u8 = builder.ensure_field("name").unwrap();
+
+        let u8_tag: u8 = SpudTypes::Number(SpudNumberTypes::U8).as_u8();
+
+        // The builders only write arrays of objects or of values, so lay out `[{"name": 1}, 5]` by hand
+        let mut items: Vec<u8> = vec![
+            SpudTypes::ObjectStart.as_u8(),
+            SpudTypes::ObjectStart.as_u8(),
+        ];
+
+        items.extend_from_slice(&[0; 10]);
+        items.extend_from_slice(&[
+            SpudTypes::FieldNameId.as_u8(),
+            name_id,
+            u8_tag,
+            1,
+            SpudTypes::ObjectEnd.as_u8(),
+            SpudTypes::ObjectEnd.as_u8(),
+            u8_tag,
+            5,
+            SpudTypes::ArrayEnd.as_u8(),
+        ]);
+
+        builder
+            .object(|obj| {
+                obj.add_raw_typed_unchecked("items", SpudTypes::ArrayStart.as_u8(), &items)?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        decoder.with_include_oid(false);
+
+        let output: String = decoder.decode_objects_flattened_ndjson().unwrap();
+
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0]["items"],
+            serde_json::json!([{ "_ref": "[0].items[0]" }, 5])
+        );
+        assert_eq!(lines[1]["_path"], "[0].items[0]");
+        assert_eq!(lines[1]["name"], 1);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_decode_flat() {
+        use indexmap::IndexMap;
+
+        use crate::{SpudBuilderSync, SpudObjectArraySync};
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("id", 1u8)?;
+
+                obj.object("user", |user| {
+                    user.add_value("name", "Alice")?;
+
+                    user.object("address", |address| {
+                        address.add_value("city", "Rome")?;
+
+                        Ok(())
+                    })?;
+
+                    Ok(())
+                })?;
+
+                obj.add_value("tags", vec!["a", "b"])?;
+                obj.add_value("empty", Vec::<u8>::new())?;
+
+                obj.add_object_array("items", |items: &SpudObjectArraySync| {
+                    items.push_object(|item| {
+                        item.add_value("sku", 7u8)?;
+
+                        Ok(())
+                    })?;
+
+                    Ok(())
+                })?;
+
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        decoder.with_include_oid(false);
+
+        let objects: Vec<IndexMap<String, serde_json::Value>> = decoder.decode_flat("/").unwrap();
+
+        assert_eq!(objects.len(), 1);
+
+        assert_eq!(
+            objects[0].keys().collect::<Vec<&String>>(),
+            [
+                "id",
+                "user/name",
+                "user/address/city",
+                "tags[0]",
+                "tags[1]",
+                "empty",
+                "items[0]/sku"
+            ]
+        );
+
+        assert_eq!(objects[0]["user/address/city"], "Rome");
+        assert_eq!(objects[0]["tags[1]"], "b");
+        assert_eq!(objects[0]["empty"], serde_json::json!([]));
+        assert_eq!(objects[0]["items[0]/sku"], 7);
+    }
+
     #[cfg(feature = "sync")]
     #[test]
     fn test_sync_encoder_to_sync_decoder() {
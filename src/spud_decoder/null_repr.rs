@@ -0,0 +1,11 @@
+/// How `Null` values are rendered in the decoded JSON.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub enum NullRepr {
+    /// A JSON `null`.
+    #[default]
+    JsonNull,
+    /// A sentinel string, e.g. `"N/A"`.
+    String(String),
+    /// The field is left out of its object, `Null` array elements are still rendered as `null`.
+    Omit,
+}
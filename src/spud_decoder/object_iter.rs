@@ -0,0 +1,60 @@
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::{SpudDecoder, SpudError};
+
+/// A decoded top-level object paired with its byte offset in the original file.
+type OffsetObject = (usize, IndexMap<String, Value>);
+
+/// Iterator over the top-level objects of a `SpudDecoder`, created by `SpudDecoder::objects`.
+///
+/// Each item is the object's byte offset in the original file paired with its decoded fields.
+/// Objects are located and decoded lazily, and the iterator stops after the first error.
+#[derive(Debug)]
+pub struct SpudObjectIter<'a> {
+    decoder: &'a SpudDecoder,
+    position: usize,
+    finished: bool,
+}
+
+impl<'a> SpudObjectIter<'a> {
+    pub(crate) fn new(decoder: &'a SpudDecoder) -> Self {
+        Self {
+            decoder,
+            position: 0,
+            finished: false,
+        }
+    }
+
+    fn decode_next(&mut self) -> Result<Option<OffsetObject>, SpudError> {
+        let Some(range) = self.decoder.next_object_range(self.position)? else {
+            return Ok(None);
+        };
+
+        self.position = range.end + self.decoder.checksum_len();
+
+        let offset: usize = self.decoder.body_offset() + range.start;
+
+        let object: IndexMap<String, Value> = self.decoder.decode_object_at(range)?;
+
+        Ok(Some((offset, object)))
+    }
+}
+
+impl Iterator for SpudObjectIter<'_> {
+    type Item = Result<OffsetObject, SpudError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let next: Option<Self::Item> = self.decode_next().transpose();
+
+        if !matches!(next, Some(Ok(_))) {
+            self.finished = true;
+        }
+
+        next
+    }
+}
@@ -0,0 +1,267 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use std::ops::Range;
+
+use crate::{
+    SpudDecoder, SpudError,
+    functions::crc32,
+    spud_builder::SpudTypesExt,
+    spud_decoder::{DecoderObject, DecoderOptions},
+    spud_types::SpudTypes,
+    types::Capabilities,
+};
+
+/// Replaces the value of a field in an encoded SPUD file without re-encoding the whole file.
+///
+/// The patch is only possible when the new value encodes to exactly as many bytes as the old one,
+/// e.g. a number of the same width or a string of the same length. In files with object checksums,
+/// the checksum of the patched object is updated as well.
+///
+/// # Arguments
+///
+/// * `bytes` - The encoded SPUD file to patch in place.
+/// * `object_index` - The index of the top-level object containing the field.
+/// * `field` - The name of the field to patch, only fields directly on the object are considered.
+/// * `new_value` - The value to write, which must implement the `SpudTypesExt` trait.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "sync")] {
+/// use spud_rs::{SpudBuilderSync, patch_field};
+///
+/// let builder = SpudBuilderSync::new();
+///
+/// builder.object(|obj| {
+///     obj.add_value("count", 1u32)?;
+///
+///     Ok(())
+/// }).unwrap();
+///
+/// let mut encoded_bytes: Vec<u8> = builder.encode().unwrap();
+///
+/// patch_field(&mut encoded_bytes, 0, "count", 2u32).unwrap();
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be parsed, if the object or field cannot be found,
+/// or if the new value's encoded length differs from the old one, in which case the file has to be re-encoded.
+pub fn patch_field(
+    bytes: &mut [u8],
+    object_index: usize,
+    field: &str,
+    new_value: impl SpudTypesExt,
+) -> Result<(), SpudError> {
+    let decoder: SpudDecoder = SpudDecoder::new(bytes)?;
+
+    let body_offset: usize = decoder.body_offset();
+
+    let object_range: Range<usize> = decoder
+        .object_ranges()?
+        .into_iter()
+        .nth(object_index)
+        .ok_or_else(|| {
+            SpudError::DecodingError(format!("Object at index {object_index} not found"))
+        })?;
+
+    let field_id: u8 = decoder
+        .field_names
+        .iter()
+        .find(|(_, name)| name.as_str() == field)
+        .map(|(id, _)| *id)
+        .ok_or_else(|| SpudError::DecodingError(format!("Field '{field}' not found")))?;
+
+    let options: DecoderOptions = DecoderOptions {
+        object_versions: decoder
+            .capabilities()
+            .contains(Capabilities::OBJECT_VERSION),
+        ..DecoderOptions::default()
+    };
+
+    let mut object: DecoderObject<'_> = DecoderObject::new(
+        &decoder.file_contents[object_range.clone()],
+        &decoder.field_names,
+        &options,
+    );
+
+    let value_range: Range<usize> = object.find_field_value(field_id)?.ok_or_else(|| {
+        SpudError::DecodingError(format!(
+            "Field '{field}' not found in object at index {object_index}"
+        ))
+    })?;
+
+    let mut new_bytes: Vec<u8> = Vec::new();
+
+    new_value.write_spud_bytes(&mut new_bytes);
+
+    if new_bytes.len() != value_range.len() {
+        return Err(SpudError::EncodingError(format!(
+            "Cannot patch field '{field}' in place: the new value takes {} bytes but the old one takes {}, re-encode the file instead",
+            new_bytes.len(),
+            value_range.len()
+        )));
+    }
+
+    let start: usize = body_offset + object_range.start + value_range.start;
+
+    bytes[start..start + new_bytes.len()].copy_from_slice(&new_bytes);
+
+    if decoder.capabilities().contains(Capabilities::CHECKSUM) {
+        let object_start: usize = body_offset + object_range.start;
+        let object_end: usize = body_offset + object_range.end;
+
+        let checksum: u32 = crc32(&bytes[object_start..object_end]);
+
+        bytes[object_end..object_end + 4].copy_from_slice(&checksum.to_le_bytes());
+    }
+
+    Ok(())
+}
+
+impl DecoderObject<'_> {
+    /// Walks the top-level fields of the object and returns the byte range of the value stored under `field_id`.
+    pub(crate) fn find_field_value(
+        &mut self,
+        field_id: u8,
+    ) -> Result<Option<Range<usize>>, SpudError> {
+        self.read_object_header()?;
+
+        while self.index < self.contents.len() {
+            if self.contents.get(self.index) == Some(&SpudTypes::ObjectEnd.as_u8())
+                && self.contents.get(self.index + 1) == Some(&SpudTypes::ObjectEnd.as_u8())
+            {
+                break;
+            }
+
+            let is_target: bool = self.current_byte == SpudTypes::FieldNameId.as_u8()
+                && self.contents.get(self.index + 1) == Some(&field_id);
+
+            self.decode_byte(self.current_byte)?;
+
+            let value_start: usize = self.index;
+
+            self.decode_byte(self.current_byte)?;
+
+            if is_target {
+                return Ok(Some(value_start..self.index));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(all(test, feature = "sync"))]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_patch_field_same_length() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("name", types::SpudString::from("Alice"))?;
+                obj.add_value("age", 30u8)?;
+                Ok(())
+            })
+            .unwrap();
+
+        builder
+            .object(|obj| {
+                obj.add_value("name", types::SpudString::from("Bob"))?;
+                obj.add_value("age", 40u8)?;
+                Ok(())
+            })
+            .unwrap();
+
+        let mut encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        patch_field(&mut encoded_bytes, 1, "age", 41u8).unwrap();
+        patch_field(
+            &mut encoded_bytes,
+            0,
+            "name",
+            types::SpudString::from("Alex"),
+        )
+        .unwrap_err();
+        patch_field(
+            &mut encoded_bytes,
+            0,
+            "name",
+            types::SpudString::from("Alina"),
+        )
+        .unwrap();
+
+        let mut decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(decoder.decode(false, true).unwrap()).unwrap();
+
+        assert_eq!(output[0]["name"], "Alina");
+        assert_eq!(output[0]["age"], 30);
+        assert_eq!(output[1]["name"], "Bob");
+        assert_eq!(output[1]["age"], 41);
+    }
+
+    #[test]
+    fn test_patch_field_length_changed() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("age", 30u8)?;
+                Ok(())
+            })
+            .unwrap();
+
+        let mut encoded_bytes: Vec<u8> = builder.encode().unwrap();
+        let original_bytes: Vec<u8> = encoded_bytes.clone();
+
+        let result: Result<(), SpudError> = patch_field(&mut encoded_bytes, 0, "age", 300u16);
+
+        assert!(matches!(result, Err(SpudError::EncodingError(_))));
+        assert_eq!(encoded_bytes, original_bytes);
+    }
+
+    #[test]
+    fn test_patch_field_updates_checksum() {
+        let mut builder = SpudBuilderSync::new();
+
+        builder.with_object_checksums();
+
+        builder
+            .object(|obj| {
+                obj.add_value("age", 30u8)?;
+                Ok(())
+            })
+            .unwrap();
+
+        let mut encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        patch_field(&mut encoded_bytes, 0, "age", 31u8).unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        assert_eq!(decoder.decode_to_value(false).unwrap()["age"], 31);
+    }
+
+    #[test]
+    fn test_patch_field_not_found() {
+        let builder = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("age", 30u8)?;
+                Ok(())
+            })
+            .unwrap();
+
+        let mut encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        assert!(patch_field(&mut encoded_bytes, 0, "missing", 1u8).is_err());
+        assert!(patch_field(&mut encoded_bytes, 1, "age", 1u8).is_err());
+    }
+}
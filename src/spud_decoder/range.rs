@@ -0,0 +1,98 @@
+use std::ops::{Range, RangeInclusive};
+
+use serde_json::Value;
+
+use crate::SpudError;
+
+/// Rebuilds a `Range<i64>` from the two-element array a `Range` is encoded as.
+///
+/// # Examples
+///
+/// ```rust
+/// use spud_rs::range_from_value;
+///
+/// let value = serde_json::json!([0, 100]);
+///
+/// assert_eq!(range_from_value(&value).unwrap(), 0..100);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the value is not an array of exactly two integers
+pub fn range_from_value(value: &Value) -> Result<Range<i64>, SpudError> {
+    let (start, end): (i64, i64) = range_bounds(value)?;
+
+    Ok(start..end)
+}
+
+/// Rebuilds a `RangeInclusive<i64>` from the two-element array a `RangeInclusive` is encoded as.
+///
+/// # Examples
+///
+/// ```rust
+/// use spud_rs::range_inclusive_from_value;
+///
+/// let value = serde_json::json!([1, 5]);
+///
+/// assert_eq!(range_inclusive_from_value(&value).unwrap(), 1..=5);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the value is not an array of exactly two integers
+pub fn range_inclusive_from_value(value: &Value) -> Result<RangeInclusive<i64>, SpudError> {
+    let (start, end): (i64, i64) = range_bounds(value)?;
+
+    Ok(start..=end)
+}
+
+fn range_bounds(value: &Value) -> Result<(i64, i64), SpudError> {
+    match value.as_array().map(Vec::as_slice) {
+        Some([start, end]) => match (start.as_i64(), end.as_i64()) {
+            (Some(start), Some(end)) => Ok((start, end)),
+            _ => Err(SpudError::DecodingError(format!(
+                "Range bounds must be integers, got {value}"
+            ))),
+        },
+        _ => Err(SpudError::DecodingError(format!(
+            "Expected a two-element array for a range, got {value}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_range_round_trip() {
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        builder
+            .object(|obj| {
+                obj.add_value("range", 0_i64..100)?;
+                obj.add_value("inclusive", 1_i64..=5)?;
+                Ok(())
+            })
+            .unwrap();
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let decoder: SpudDecoder = SpudDecoder::new(&encoded_bytes).unwrap();
+
+        let output: Vec<serde_json::Value> = decoder.decode_values().unwrap();
+
+        assert_eq!(range_from_value(&output[0]["range"]).unwrap(), 0..100);
+        assert_eq!(
+            range_inclusive_from_value(&output[0]["inclusive"]).unwrap(),
+            1..=5
+        );
+    }
+
+    #[test]
+    fn test_range_invalid() {
+        assert!(range_from_value(&serde_json::json!([1, 2, 3])).is_err());
+        assert!(range_from_value(&serde_json::json!([1, "2"])).is_err());
+    }
+}
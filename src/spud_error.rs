@@ -1,13 +1,16 @@
-use std::{
-    array::TryFromSliceError, error::Error, fmt, num::TryFromIntError, string::FromUtf8Error,
-};
+use alloc::string::{FromUtf8Error, String};
+use core::{array::TryFromSliceError, error::Error, fmt, num::TryFromIntError};
 
 #[derive(Debug)]
 pub enum SpudError {
+    #[cfg(feature = "std")]
     Io(std::io::Error),
     FromUtf8(FromUtf8Error),
+    #[cfg(feature = "std")]
     SerdeJson(serde_json::Error),
+    #[cfg(feature = "std")]
     GetRandom(getrandom::Error),
+    #[cfg(feature = "std")]
     Bs58(bs58::decode::Error),
     TryFromInt(TryFromIntError),
     TryFromSlice(TryFromSliceError),
@@ -23,10 +26,14 @@ pub enum SpudError {
 impl fmt::Display for SpudError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            #[cfg(feature = "std")]
             SpudError::Io(err) => write!(f, "IO error: {err}"),
             SpudError::FromUtf8(err) => write!(f, "UTF-8 conversion error: {err}"),
+            #[cfg(feature = "std")]
             SpudError::SerdeJson(err) => write!(f, "JSON serialization error: {err}"),
+            #[cfg(feature = "std")]
             SpudError::GetRandom(err) => write!(f, "getrandom error: {err}"),
+            #[cfg(feature = "std")]
             SpudError::Bs58(err) => write!(f, "Base58 decoding error: {err}"),
             SpudError::TryFromInt(err) => write!(f, "Integer conversion error: {err}"),
             SpudError::TryFromSlice(err) => write!(f, "Slice conversion error: {err}"),
@@ -44,10 +51,14 @@ impl fmt::Display for SpudError {
 impl Error for SpudError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
+            #[cfg(feature = "std")]
             SpudError::Io(err) => Some(err),
             SpudError::FromUtf8(err) => Some(err),
+            #[cfg(feature = "std")]
             SpudError::SerdeJson(err) => Some(err),
+            #[cfg(feature = "std")]
             SpudError::GetRandom(err) => Some(err),
+            #[cfg(feature = "std")]
             SpudError::Bs58(err) => Some(err),
             SpudError::TryFromInt(err) => Some(err),
             SpudError::TryFromSlice(err) => Some(err),
@@ -56,6 +67,7 @@ impl Error for SpudError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<std::io::Error> for SpudError {
     fn from(err: std::io::Error) -> SpudError {
         SpudError::Io(err)
@@ -68,18 +80,21 @@ impl From<FromUtf8Error> for SpudError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<serde_json::Error> for SpudError {
     fn from(err: serde_json::Error) -> SpudError {
         SpudError::SerdeJson(err)
     }
 }
 
+#[cfg(feature = "std")]
 impl From<getrandom::Error> for SpudError {
     fn from(err: getrandom::Error) -> SpudError {
         SpudError::GetRandom(err)
     }
 }
 
+#[cfg(feature = "std")]
 impl From<bs58::decode::Error> for SpudError {
     fn from(err: bs58::decode::Error) -> SpudError {
         SpudError::Bs58(err)
@@ -98,8 +113,8 @@ impl From<TryFromSliceError> for SpudError {
     }
 }
 
-impl From<SpudError> for std::fmt::Error {
-    fn from(_: SpudError) -> std::fmt::Error {
-        std::fmt::Error
+impl From<SpudError> for fmt::Error {
+    fn from(_: SpudError) -> fmt::Error {
+        fmt::Error
     }
 }
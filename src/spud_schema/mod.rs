@@ -0,0 +1,5 @@
+mod schema;
+mod schema_types;
+
+pub use schema::SpudSchema;
+pub use schema_types::SpudSchemaTypes;
@@ -0,0 +1,104 @@
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::{SpudError, spud_types::SpudTypes};
+
+use super::SpudSchemaTypes;
+
+/// Describes the fields an object is expected to have and the type of each one.
+///
+/// Fields declared with `SpudSchemaTypes::Optional` may be missing, every other field is required.
+/// Fields that are not declared in the schema are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use spud_rs::{SpudSchema, SpudSchemaTypes};
+///
+/// let mut schema = SpudSchema::new();
+///
+/// schema
+///     .add_field("name", SpudSchemaTypes::String)
+///     .add_field("age", SpudSchemaTypes::Optional(Box::new(SpudSchemaTypes::Number)));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SpudSchema {
+    fields: IndexMap<String, SpudSchemaTypes>,
+}
+
+impl SpudSchema {
+    #[must_use]
+    /// Creates an empty `SpudSchema`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares a field, replacing any previous declaration with the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - The name of the field.
+    /// * `field_type` - The type the field's value must have.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the `SpudSchema`, allowing for method chaining.
+    pub fn add_field(&mut self, field_name: &str, field_type: SpudSchemaTypes) -> &mut Self {
+        self.fields.insert(field_name.to_owned(), field_type);
+
+        self
+    }
+
+    #[must_use]
+    /// Returns the declared type of a field, if the schema has it.
+    pub fn get(&self, field_name: &str) -> Option<&SpudSchemaTypes> {
+        self.fields.get(field_name)
+    }
+
+    /// Returns the declared fields in declaration order.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &SpudSchemaTypes)> {
+        self.fields
+            .iter()
+            .map(|(name, field_type)| (name.as_str(), field_type))
+    }
+
+    /// Checks that a field is declared in the schema with a type `found` satisfies.
+    pub(crate) fn check_field(&self, field_name: &str, found: SpudTypes) -> Result<(), SpudError> {
+        match self.fields.get(field_name) {
+            Some(expected) if !expected.matches(found) => Err(SpudError::ValidationError(format!(
+                "field '{field_name}' expected {expected}, found {}",
+                SpudSchemaTypes::describe(found)
+            ))),
+            Some(_) => Ok(()),
+            None => Err(SpudError::ValidationError(format!(
+                "field '{field_name}' is not declared in the schema"
+            ))),
+        }
+    }
+
+    /// Checks an object decoded with type annotations against the schema, reporting the first violation.
+    pub(crate) fn validate(&self, fields: &IndexMap<String, Value>) -> Result<(), SpudError> {
+        self.validate_fields("", |field_name| fields.get(field_name))
+    }
+
+    /// Checks the fields `lookup` resolves against the schema, prefixing every reported field name with `prefix`.
+    pub(crate) fn validate_fields<'v>(
+        &self,
+        prefix: &str,
+        lookup: impl Fn(&str) -> Option<&'v Value>,
+    ) -> Result<(), SpudError> {
+        for (field_name, expected) in &self.fields {
+            match lookup(field_name) {
+                Some(value) => expected.check_value(&format!("{prefix}{field_name}"), value)?,
+                None if !expected.is_optional() => {
+                    return Err(SpudError::ValidationError(format!(
+                        "missing required field '{prefix}{field_name}'"
+                    )));
+                }
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::{SpudError, spud_schema::SpudSchema, spud_types::SpudTypes};
+
+/// The types a field of a `SpudSchema` can be declared with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpudSchemaTypes {
+    String,
+    /// Any integer or float width.
+    Number,
+    Bool,
+    Null,
+    BinaryBlob,
+    Date,
+    Time,
+    DateTime,
+    Duration,
+    Decimal,
+    /// An array whose every element holds the wrapped type.
+    Array(Box<SpudSchemaTypes>),
+    /// A nested object that has to conform to the wrapped schema.
+    Object(SpudSchema),
+    /// A field that may be absent or null, and otherwise holds the wrapped type.
+    Optional(Box<SpudSchemaTypes>),
+}
+
+impl SpudSchemaTypes {
+    /// Maps an encoded type to the schema type it satisfies, every number width collapsing to `Number`
+    /// and enum variants to `String`. Arrays, objects and types a schema cannot declare, such as UUIDs, map to `None`.
+    pub(crate) fn from_spud_type(spud_type: SpudTypes) -> Option<SpudSchemaTypes> {
+        match spud_type {
+            SpudTypes::String | SpudTypes::Enum => Some(SpudSchemaTypes::String),
+            SpudTypes::Number(_) => Some(SpudSchemaTypes::Number),
+            SpudTypes::Bool => Some(SpudSchemaTypes::Bool),
+            SpudTypes::Null => Some(SpudSchemaTypes::Null),
+            SpudTypes::BinaryBlob => Some(SpudSchemaTypes::BinaryBlob),
+            SpudTypes::Date => Some(SpudSchemaTypes::Date),
+            SpudTypes::Time => Some(SpudSchemaTypes::Time),
+            SpudTypes::DateTime => Some(SpudSchemaTypes::DateTime),
+            SpudTypes::Duration => Some(SpudSchemaTypes::Duration),
+            SpudTypes::Decimal => Some(SpudSchemaTypes::Decimal),
+            _ => None,
+        }
+    }
+
+    /// Checks an encoded type against the declaration, without looking inside arrays and objects.
+    pub(crate) fn matches(&self, spud_type: SpudTypes) -> bool {
+        match self {
+            SpudSchemaTypes::Optional(inner) => {
+                spud_type == SpudTypes::Null || inner.matches(spud_type)
+            }
+            SpudSchemaTypes::Array(_) => spud_type == SpudTypes::ArrayStart,
+            SpudSchemaTypes::Object(_) => spud_type == SpudTypes::ObjectStart,
+            expected => Self::from_spud_type(spud_type).as_ref() == Some(expected),
+        }
+    }
+
+    /// Checks a value decoded with type annotations against the declaration, descending into arrays and objects.
+    ///
+    /// `path` names the value in error messages, e.g. `address.city` or `tags[1]`.
+    pub(crate) fn check_value(&self, path: &str, value: &Value) -> Result<(), SpudError> {
+        let (spud_type, inner_value): (SpudTypes, &Value) = annotated_type(value)?;
+
+        if !self.matches(spud_type) {
+            return Err(SpudError::ValidationError(format!(
+                "field '{path}' expected {self}, found {}",
+                Self::describe(spud_type)
+            )));
+        }
+
+        match self {
+            SpudSchemaTypes::Optional(inner) if spud_type != SpudTypes::Null => {
+                inner.check_value(path, value)
+            }
+            SpudSchemaTypes::Array(element_type) => inner_value
+                .as_array()
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .try_for_each(|(index, element)| {
+                    element_type.check_value(&format!("{path}[{index}]"), element)
+                }),
+            SpudSchemaTypes::Object(schema) => {
+                let fields = inner_value.as_object();
+
+                schema.validate_fields(&format!("{path}."), |field_name| {
+                    fields.and_then(|fields| fields.get(field_name))
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Describes an encoded type the way a mismatch is reported, in schema terms when it has a schema type.
+    pub(crate) fn describe(spud_type: SpudTypes) -> String {
+        Self::from_spud_type(spud_type).map_or_else(
+            || spud_type.name().to_owned(),
+            |schema_type| schema_type.to_string(),
+        )
+    }
+
+    pub(crate) fn is_optional(&self) -> bool {
+        matches!(self, SpudSchemaTypes::Optional(_))
+    }
+}
+
+/// Splits a value decoded with `annotate_types` into its SPUD type and the bare value.
+fn annotated_type(value: &Value) -> Result<(SpudTypes, &Value), SpudError> {
+    let type_name: Option<&str> = value.get("type").and_then(Value::as_str);
+
+    let spud_type: Option<SpudTypes> = type_name.and_then(|type_name| {
+        (0..=u8::MAX)
+            .filter_map(SpudTypes::from_u8)
+            .find(|spud_type| spud_type.name() == type_name)
+    });
+
+    match (spud_type, value.get("value")) {
+        (Some(spud_type), Some(inner_value)) => Ok((spud_type, inner_value)),
+        _ => Err(SpudError::DecodingError(format!(
+            "expected a type-annotated value, found {value}"
+        ))),
+    }
+}
+
+impl fmt::Display for SpudSchemaTypes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpudSchemaTypes::String => write!(f, "String"),
+            SpudSchemaTypes::Number => write!(f, "Number"),
+            SpudSchemaTypes::Bool => write!(f, "Bool"),
+            SpudSchemaTypes::Null => write!(f, "Null"),
+            SpudSchemaTypes::BinaryBlob => write!(f, "BinaryBlob"),
+            SpudSchemaTypes::Date => write!(f, "Date"),
+            SpudSchemaTypes::Time => write!(f, "Time"),
+            SpudSchemaTypes::DateTime => write!(f, "DateTime"),
+            SpudSchemaTypes::Duration => write!(f, "Duration"),
+            SpudSchemaTypes::Decimal => write!(f, "Decimal"),
+            SpudSchemaTypes::Array(inner) => write!(f, "Array<{inner}>"),
+            SpudSchemaTypes::Object(_) => write!(f, "Object"),
+            SpudSchemaTypes::Optional(inner) => write!(f, "Optional<{inner}>"),
+        }
+    }
+}
@@ -1,11 +1,28 @@
+/// The type tags of the SPUD format, the byte written before every value and structural marker.
+///
+/// Meant for tools that read SPUD files without going through `SpudDecoder`, such as hex viewers or
+/// alternative decoders. Use `from_u8` and `as_u8` to convert from and to the byte on disk,
+/// new tags may be added in later format versions.
+///
+/// # Examples
+///
+/// ```rust
+/// use spud_rs::{SpudNumberTypes, SpudTypes};
+///
+/// assert_eq!(SpudTypes::from_u8(0x0A), Some(SpudTypes::Number(SpudNumberTypes::U16)));
+/// assert_eq!(SpudTypes::ObjectStart.as_u8(), 0x12);
+/// assert_eq!(SpudTypes::from_u8(0xFF), None);
+/// ```
 #[repr(u8)]
+#[non_exhaustive]
 #[derive(Debug, PartialEq, Copy, Clone)]
-pub(crate) enum SpudTypes {
+pub enum SpudTypes {
     // Core Data Types
     Null = 0x03,
     Bool = 0x04,
     Number(SpudNumberTypes),
     Decimal = 0x15,
+    Char = 0x23,
 
     // Variable-Length Types
     String = 0x0F,
@@ -15,6 +32,14 @@ pub(crate) enum SpudTypes {
     Date = 0x16,
     Time = 0x17,
     DateTime = 0x18,
+    Duration = 0x24,
+
+    // Identifier Types
+    Uuid = 0x22,
+    IpAddr = 0x25,
+
+    // Interned Types
+    Enum = 0x21,
 
     // Composite Type Delimiters
     ArrayStart = 0x10,
@@ -27,8 +52,10 @@ pub(crate) enum SpudTypes {
     FieldNameListEnd = 0x01,
 }
 
+/// The numeric type tags of the SPUD format, wrapped in `SpudTypes::Number`.
+#[non_exhaustive]
 #[derive(Debug, PartialEq, Copy, Clone)]
-pub(crate) enum SpudNumberTypes {
+pub enum SpudNumberTypes {
     I8 = 0x05,
     I16 = 0x06,
     I32 = 0x07,
@@ -45,15 +72,14 @@ pub(crate) enum SpudNumberTypes {
 
 impl SpudTypes {
     #[must_use]
+    /// Returns the type tagged by `value`, or `None` if the byte is not a known tag.
     pub fn from_u8(value: u8) -> Option<SpudTypes> {
         match value {
             0x01 => Some(SpudTypes::FieldNameListEnd),
             0x02 => Some(SpudTypes::FieldNameId),
             0x03 => Some(SpudTypes::Null),
             0x04 => Some(SpudTypes::Bool),
-            5_u8..=14_u8 | 0x19 | 0x20 => {
-                Some(SpudTypes::Number(SpudNumberTypes::from_u8(value).unwrap()))
-            }
+            5_u8..=14_u8 | 0x19 | 0x20 => SpudNumberTypes::from_u8(value).map(SpudTypes::Number),
             0x0F => Some(SpudTypes::String),
             0x10 => Some(SpudTypes::ArrayStart),
             0x11 => Some(SpudTypes::ArrayEnd),
@@ -64,11 +90,17 @@ impl SpudTypes {
             0x16 => Some(SpudTypes::Date),
             0x17 => Some(SpudTypes::Time),
             0x18 => Some(SpudTypes::DateTime),
+            0x21 => Some(SpudTypes::Enum),
+            0x22 => Some(SpudTypes::Uuid),
+            0x23 => Some(SpudTypes::Char),
+            0x24 => Some(SpudTypes::Duration),
+            0x25 => Some(SpudTypes::IpAddr),
             _ => None,
         }
     }
 
     #[must_use]
+    /// Returns the byte the type is tagged with on disk.
     pub fn as_u8(self) -> u8 {
         match self {
             SpudTypes::Null => 0x03,
@@ -80,6 +112,11 @@ impl SpudTypes {
             SpudTypes::Date => 0x16,
             SpudTypes::Time => 0x17,
             SpudTypes::DateTime => 0x18,
+            SpudTypes::Duration => 0x24,
+            SpudTypes::Enum => 0x21,
+            SpudTypes::Uuid => 0x22,
+            SpudTypes::IpAddr => 0x25,
+            SpudTypes::Char => 0x23,
             SpudTypes::ArrayStart => 0x10,
             SpudTypes::ArrayEnd => 0x11,
             SpudTypes::ObjectStart => 0x12,
@@ -88,10 +125,36 @@ impl SpudTypes {
             SpudTypes::FieldNameListEnd => 0x01,
         }
     }
+
+    #[must_use]
+    /// Returns the name the type is reported with in annotated decode output.
+    pub fn name(self) -> &'static str {
+        match self {
+            SpudTypes::Null => "Null",
+            SpudTypes::Bool => "Bool",
+            SpudTypes::Number(num_type) => num_type.name(),
+            SpudTypes::Decimal => "Decimal",
+            SpudTypes::String => "String",
+            SpudTypes::BinaryBlob => "BinaryBlob",
+            SpudTypes::Date => "Date",
+            SpudTypes::Time => "Time",
+            SpudTypes::DateTime => "DateTime",
+            SpudTypes::Duration => "Duration",
+            SpudTypes::Enum => "Enum",
+            SpudTypes::Uuid => "Uuid",
+            SpudTypes::IpAddr => "IpAddr",
+            SpudTypes::Char => "Char",
+            SpudTypes::ArrayStart | SpudTypes::ArrayEnd => "Array",
+            SpudTypes::ObjectStart | SpudTypes::ObjectEnd => "Object",
+            SpudTypes::FieldNameId => "FieldNameId",
+            SpudTypes::FieldNameListEnd => "FieldNameListEnd",
+        }
+    }
 }
 
 impl SpudNumberTypes {
     #[must_use]
+    /// Returns the numeric type tagged by `value`, or `None` if the byte is not a numeric tag.
     pub fn from_u8(value: u8) -> Option<SpudNumberTypes> {
         match value {
             0x05 => Some(SpudNumberTypes::I8),
@@ -111,9 +174,41 @@ impl SpudNumberTypes {
     }
 
     #[must_use]
+    /// Returns the byte the numeric type is tagged with on disk.
     pub fn as_u8(self) -> u8 {
         self as u8
     }
+
+    #[must_use]
+    /// Returns the name the numeric type is reported with in annotated decode output.
+    pub fn name(self) -> &'static str {
+        match self {
+            SpudNumberTypes::I8 => "I8",
+            SpudNumberTypes::I16 => "I16",
+            SpudNumberTypes::I32 => "I32",
+            SpudNumberTypes::I64 => "I64",
+            SpudNumberTypes::I128 => "I128",
+            SpudNumberTypes::U8 => "U8",
+            SpudNumberTypes::U16 => "U16",
+            SpudNumberTypes::U32 => "U32",
+            SpudNumberTypes::U64 => "U64",
+            SpudNumberTypes::U128 => "U128",
+            SpudNumberTypes::F32 => "F32",
+            SpudNumberTypes::F64 => "F64",
+        }
+    }
+
+    #[must_use]
+    /// Returns the number of bytes a value of this type takes.
+    pub fn width(self) -> usize {
+        match self {
+            SpudNumberTypes::I8 | SpudNumberTypes::U8 => 1,
+            SpudNumberTypes::I16 | SpudNumberTypes::U16 => 2,
+            SpudNumberTypes::I32 | SpudNumberTypes::U32 | SpudNumberTypes::F32 => 4,
+            SpudNumberTypes::I64 | SpudNumberTypes::U64 | SpudNumberTypes::F64 => 8,
+            SpudNumberTypes::I128 | SpudNumberTypes::U128 => 16,
+        }
+    }
 }
 
 #[cfg(test)]
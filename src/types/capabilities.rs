@@ -0,0 +1,139 @@
+use std::{fmt, ops::BitOr};
+
+use crate::SpudError;
+
+/// The trailer written by files that use no optional feature.
+const NO_CAPABILITIES_TRAILER: [u8; 4] = [0xDE, 0xAD, 0xBE, 0xEF];
+
+/// Every flag defined by `Capabilities`.
+const KNOWN_BITS: u32 = 0b11_1111;
+
+/// The flags reserved for features no builder produces yet, `COMPRESSION`, `INTERNING` and `METADATA`.
+const RESERVED_BITS: u32 = 0b1101;
+
+/// The optional format features a SPUD file uses, recorded in its 4-byte trailer.
+///
+/// Files that use none of them keep the historical `0xDEADBEEF` trailer, any other trailer
+/// holds the flags as a little-endian `u32`.
+///
+/// # Examples
+///
+/// ```rust
+/// use spud_rs::types::Capabilities;
+///
+/// let capabilities = Capabilities::CHECKSUM | Capabilities::METADATA;
+///
+/// assert!(capabilities.contains(Capabilities::CHECKSUM));
+/// assert!(!capabilities.contains(Capabilities::COMPRESSION));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Capabilities(u32);
+
+impl Capabilities {
+    /// Reserved for compressed object data, not produced yet and rejected by the decoder.
+    pub const COMPRESSION: Capabilities = Capabilities(1);
+    /// Every top-level object is followed by its CRC-32, see `with_object_checksums`.
+    pub const CHECKSUM: Capabilities = Capabilities(1 << 1);
+    /// Reserved for interned string values, not produced yet and rejected by the decoder.
+    pub const INTERNING: Capabilities = Capabilities(1 << 2);
+    /// Reserved for file-level metadata, not produced yet and rejected by the decoder.
+    pub const METADATA: Capabilities = Capabilities(1 << 3);
+    /// Every top-level object starts with its format version byte, see `with_object_versions`.
+    pub const OBJECT_VERSION: Capabilities = Capabilities(1 << 4);
+    /// The field-name table is written after the objects, see `with_footer_field_table`.
+    pub const FOOTER_FIELD_TABLE: Capabilities = Capabilities(1 << 5);
+
+    #[must_use]
+    /// Returns a set with no capability.
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    #[must_use]
+    /// Returns `true` if no capability is set.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    #[must_use]
+    /// Returns `true` if every capability in `other` is set.
+    pub fn contains(self, other: Capabilities) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Sets every capability in `other`.
+    pub fn insert(&mut self, other: Capabilities) {
+        self.0 |= other.0;
+    }
+
+    /// Clears every capability in `other`.
+    pub fn remove(&mut self, other: Capabilities) {
+        self.0 &= !other.0;
+    }
+
+    #[must_use]
+    /// Returns the raw flags.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Reads the flags from a trailer, which is either `0xDEADBEEF` or a non-empty set of known flags.
+    pub(crate) fn from_trailer(trailer: [u8; 4]) -> Result<Self, SpudError> {
+        if trailer == NO_CAPABILITIES_TRAILER {
+            return Ok(Self::empty());
+        }
+
+        let bits: u32 = u32::from_le_bytes(trailer);
+
+        if bits == 0 || bits & !KNOWN_BITS != 0 {
+            return Err(SpudError::InvalidSpudFile(format!(
+                "unrecognised trailer {trailer:02X?}, the file may be truncated"
+            )));
+        }
+
+        Ok(Self(bits))
+    }
+
+    /// Returns the reserved flags that are set, which the decoder cannot honour.
+    pub(crate) fn reserved(self) -> Capabilities {
+        Self(self.0 & RESERVED_BITS)
+    }
+
+    pub(crate) fn as_trailer(self) -> [u8; 4] {
+        if self.is_empty() {
+            NO_CAPABILITIES_TRAILER
+        } else {
+            self.0.to_le_bytes()
+        }
+    }
+}
+
+impl BitOr for Capabilities {
+    type Output = Capabilities;
+
+    fn bitor(self, rhs: Capabilities) -> Capabilities {
+        Capabilities(self.0 | rhs.0)
+    }
+}
+
+impl fmt::Debug for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: [(Capabilities, &str); 6] = [
+            (Capabilities::COMPRESSION, "COMPRESSION"),
+            (Capabilities::CHECKSUM, "CHECKSUM"),
+            (Capabilities::INTERNING, "INTERNING"),
+            (Capabilities::METADATA, "METADATA"),
+            (Capabilities::OBJECT_VERSION, "OBJECT_VERSION"),
+            (Capabilities::FOOTER_FIELD_TABLE, "FOOTER_FIELD_TABLE"),
+        ];
+
+        f.debug_set()
+            .entries(
+                names
+                    .iter()
+                    .filter(|(flag, _)| self.contains(*flag))
+                    .map(|(_, name)| name),
+            )
+            .finish()
+    }
+}
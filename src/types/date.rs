@@ -1,3 +1,4 @@
+use alloc::{borrow::ToOwned, format, vec::Vec};
 use core::{fmt, str::FromStr};
 
 use chrono::{Datelike, NaiveDate, NaiveDateTime};
@@ -49,7 +50,9 @@ impl Date {
             1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
             4 | 6 | 9 | 11 => 30,
             2 => {
-                if (self.year % 4 == 0 && self.year % 100 != 0) || (self.year % 400 == 0) {
+                if (self.year.is_multiple_of(4) && !self.year.is_multiple_of(100))
+                    || self.year.is_multiple_of(400)
+                {
                     29
                 } else {
                     28
@@ -100,23 +103,28 @@ impl TryFrom<NaiveDateTime> for Date {
 }
 
 impl FromStr for Date {
-    type Err = fmt::Error;
+    type Err = SpudError;
 
     /// Parses a string in the format "YYYY-MM-DD" into a `Date` instance.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts: Vec<&str> = s.split('-').collect();
 
         if parts.len() != 3 {
-            return Err(fmt::Error);
+            return Err(SpudError::ValidationError("Invalid date format".to_owned()));
         }
 
-        let year: u16 = u16::from_str(parts[0]).map_err(|_| fmt::Error)?;
-        let month: u8 = u8::from_str(parts[1]).map_err(|_| fmt::Error)?;
-        let day: u8 = u8::from_str(parts[2]).map_err(|_| fmt::Error)?;
+        let year: u16 = u16::from_str(parts[0])
+            .map_err(|_| SpudError::ValidationError("Invalid year".to_owned()))?;
+
+        let month: u8 = u8::from_str(parts[1])
+            .map_err(|_| SpudError::ValidationError("Invalid month".to_owned()))?;
+
+        let day: u8 = u8::from_str(parts[2])
+            .map_err(|_| SpudError::ValidationError("Invalid day".to_owned()))?;
 
         let date: Date = Date { year, month, day };
 
-        date.check_validity().map_err(|_| fmt::Error)?;
+        date.check_validity()?;
 
         Ok(date)
     }
@@ -143,6 +151,8 @@ impl fmt::Display for Date {
 
 #[cfg(test)]
 mod tests {
+    use alloc::string::ToString;
+
     use chrono::NaiveTime;
 
     use super::*;
@@ -197,7 +207,7 @@ mod tests {
     #[test]
     fn test_date_from_str() {
         let date_str: &str = "2023-03-15";
-        let date: Result<Date, fmt::Error> = Date::from_str(date_str);
+        let date: Result<Date, SpudError> = Date::from_str(date_str);
 
         assert!(date.is_ok());
         assert_eq!(date.unwrap().to_string(), "2023-03-15");
@@ -206,16 +216,32 @@ mod tests {
     #[test]
     fn test_date_from_str_invalid() {
         let invalid_date_str: &str = "2023-13-15";
-        let date: Result<Date, fmt::Error> = Date::from_str(invalid_date_str);
+        let date: Result<Date, SpudError> = Date::from_str(invalid_date_str);
 
         assert!(date.is_err());
 
         let invalid_date_str: &str = "2023-02-30";
-        let date: Result<Date, fmt::Error> = Date::from_str(invalid_date_str);
+        let date: Result<Date, SpudError> = Date::from_str(invalid_date_str);
 
         assert!(date.is_err());
     }
 
+    #[test]
+    fn test_date_from_str_error_message() {
+        assert!(matches!(
+            Date::from_str("2023-13-15"),
+            Err(SpudError::ValidationError(message)) if message == "The month must be between 1 and 12"
+        ));
+        assert!(matches!(
+            Date::from_str("2023-02-30"),
+            Err(SpudError::ValidationError(message)) if message.contains("has 28 days")
+        ));
+        assert!(matches!(
+            Date::from_str("2023-03"),
+            Err(SpudError::ValidationError(message)) if message == "Invalid date format"
+        ));
+    }
+
     #[test]
     fn test_date_to_naive_date() {
         let date: Date = Date::new(2023, 3, 15).unwrap();
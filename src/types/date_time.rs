@@ -1,4 +1,7 @@
+use alloc::{borrow::ToOwned, vec::Vec};
 use core::{fmt, str::FromStr};
+#[cfg(feature = "std")]
+use std::time::SystemTime;
 
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 
@@ -26,6 +29,16 @@ impl DateTime {
         DateTime { date, time }
     }
 
+    /// Returns the current UTC date and time, read from `SystemTime::now()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current year does not fit the `Date` range.
+    #[cfg(feature = "std")]
+    pub fn now() -> Result<Self, SpudError> {
+        DateTime::try_from(chrono::DateTime::<chrono::Utc>::from(SystemTime::now()).naive_utc())
+    }
+
     pub(crate) fn as_le_bytes(self) -> Vec<u8> {
         let mut bytes: Vec<u8> = self.date.as_le_bytes();
 
@@ -46,14 +59,19 @@ impl TryFrom<NaiveDateTime> for DateTime {
 }
 
 impl FromStr for DateTime {
-    type Err = core::fmt::Error;
+    type Err = SpudError;
 
-    /// Parses a string in the format "YYYY-MM-DD HH:MM:SS.NS" into a `DateTime` instance.
+    /// Parses a string in the format "YYYY-MM-DD HH:MM:SS" or "YYYY-MM-DD HH:MM:SS.NS" into a `DateTime` instance.
+    ///
+    /// Leading and trailing whitespace is ignored, and the date and time may be separated by any run
+    /// of whitespace. Anything other than exactly one date and one time is rejected.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts: Vec<&str> = s.split_whitespace().collect();
 
         if parts.len() != 2 {
-            return Err(core::fmt::Error);
+            return Err(SpudError::ValidationError(
+                "Invalid date time format".to_owned(),
+            ));
         }
 
         let date = Date::from_str(parts[0])?;
@@ -82,6 +100,8 @@ impl fmt::Display for DateTime {
 
 #[cfg(test)]
 mod tests {
+    use alloc::string::ToString;
+
     use super::*;
 
     #[test]
@@ -113,50 +133,79 @@ mod tests {
     #[test]
     fn test_datetime_from_str() {
         let datetime_str: &str = "2023-03-15 12:30:45.500000000";
-        let datetime: Result<DateTime, fmt::Error> = DateTime::from_str(datetime_str);
+        let datetime: Result<DateTime, SpudError> = DateTime::from_str(datetime_str);
 
         assert!(datetime.is_ok());
         assert_eq!(datetime.unwrap().to_string(), datetime_str);
     }
 
+    #[test]
+    fn test_datetime_from_str_padded() {
+        let datetime: DateTime = DateTime::from_str("  2023-03-15 12:30:45\n").unwrap();
+
+        assert_eq!(datetime.to_string(), "2023-03-15 12:30:45");
+    }
+
+    #[test]
+    fn test_datetime_from_str_internal_whitespace() {
+        let datetime: DateTime = DateTime::from_str("2023-03-15 \t  12:30:45").unwrap();
+
+        assert_eq!(datetime.to_string(), "2023-03-15 12:30:45");
+
+        assert!(DateTime::from_str("2023-03-15 12:30:45 extra").is_err());
+        assert!(DateTime::from_str("2023-03-15T12:30:45").is_err());
+    }
+
     #[test]
     fn test_datetime_from_str_invalid() {
         let invalid_str: &str = "2023-13-15 12:30:45";
-        let datetime: Result<DateTime, fmt::Error> = DateTime::from_str(invalid_str);
+        let datetime: Result<DateTime, SpudError> = DateTime::from_str(invalid_str);
 
         assert!(datetime.is_err());
 
         let invalid_str: &str = "2023-02-30 12:30:45";
-        let datetime: Result<DateTime, fmt::Error> = DateTime::from_str(invalid_str);
+        let datetime: Result<DateTime, SpudError> = DateTime::from_str(invalid_str);
 
         assert!(datetime.is_err());
 
         let invalid_str: &str = "2023-03-15 25:00:00";
-        let datetime: Result<DateTime, fmt::Error> = DateTime::from_str(invalid_str);
+        let datetime: Result<DateTime, SpudError> = DateTime::from_str(invalid_str);
 
         assert!(datetime.is_err());
 
         let invalid_str: &str = "2023-03-15 12:60:00";
-        let datetime: Result<DateTime, fmt::Error> = DateTime::from_str(invalid_str);
+        let datetime: Result<DateTime, SpudError> = DateTime::from_str(invalid_str);
 
         assert!(datetime.is_err());
 
         let invalid_str: &str = "2023-03-15 12:30:60";
-        let datetime: Result<DateTime, fmt::Error> = DateTime::from_str(invalid_str);
+        let datetime: Result<DateTime, SpudError> = DateTime::from_str(invalid_str);
 
         assert!(datetime.is_err());
 
         let invalid_str: &str = "2023-03-15 12:30:45.1000000000";
-        let datetime: Result<DateTime, fmt::Error> = DateTime::from_str(invalid_str);
+        let datetime: Result<DateTime, SpudError> = DateTime::from_str(invalid_str);
 
         assert!(datetime.is_err());
 
         let invalid_str: &str = "2023-03-15 12:30";
-        let datetime: Result<DateTime, fmt::Error> = DateTime::from_str(invalid_str);
+        let datetime: Result<DateTime, SpudError> = DateTime::from_str(invalid_str);
 
         assert!(datetime.is_err());
     }
 
+    #[test]
+    fn test_datetime_from_str_error_message() {
+        assert!(matches!(
+            DateTime::from_str("2023-02-30 12:30:45"),
+            Err(SpudError::ValidationError(message)) if message.contains("has 28 days")
+        ));
+        assert!(matches!(
+            DateTime::from_str("2023-03-15 25:00:00"),
+            Err(SpudError::ValidationError(message)) if message == "Hour must be between 0 and 23"
+        ));
+    }
+
     #[test]
     fn test_datetime_to_naive_date_time() {
         let date: Date = Date::new(2023, 3, 15).unwrap();
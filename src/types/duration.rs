@@ -0,0 +1,229 @@
+use alloc::{borrow::ToOwned, format, vec::Vec};
+use core::{fmt, str::FromStr, time};
+
+use crate::SpudError;
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// The units accepted by `Duration::from_str` and written by its `Display`, largest first.
+const UNITS: [(&str, u64); 6] = [
+    ("h", 3_600 * NANOS_PER_SECOND),
+    ("m", 60 * NANOS_PER_SECOND),
+    ("s", NANOS_PER_SECOND),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+
+/// A struct representing an elapsed, non-negative duration as a count of nanoseconds.
+/// This struct can be created from `core::time::Duration`, and can also be parsed from a
+/// string such as `1h30m` or `250ms`.
+///
+/// # Notes
+/// - It shares the `Duration` type tag with `chrono::TimeDelta`, so it decodes the same way.
+/// - The longest representable duration is `u64::MAX` nanoseconds, about 584 years.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration {
+    nanoseconds: u64,
+}
+
+impl Duration {
+    #[must_use]
+    /// Creates a new `Duration` from a count of nanoseconds.
+    pub const fn from_nanos(nanoseconds: u64) -> Self {
+        Duration { nanoseconds }
+    }
+
+    #[must_use]
+    /// Returns the duration as a count of nanoseconds.
+    pub const fn as_nanos(self) -> u64 {
+        self.nanoseconds
+    }
+
+    pub(crate) fn as_le_bytes(self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(8 + 4);
+
+        // At most `u64::MAX / 10^9` seconds and a remainder below one billion, both always fit
+        let seconds: i64 = (self.nanoseconds / NANOS_PER_SECOND).cast_signed();
+        #[allow(clippy::cast_possible_truncation)]
+        let nanoseconds: u32 = (self.nanoseconds % NANOS_PER_SECOND) as u32;
+
+        bytes.extend_from_slice(&seconds.to_le_bytes());
+        bytes.extend_from_slice(&nanoseconds.to_le_bytes());
+
+        bytes
+    }
+}
+
+impl TryFrom<time::Duration> for Duration {
+    type Error = SpudError;
+
+    fn try_from(duration: time::Duration) -> Result<Self, Self::Error> {
+        u64::try_from(duration.as_nanos())
+            .map(Duration::from_nanos)
+            .map_err(|_| {
+                SpudError::ValidationError("Duration exceeds u64::MAX nanoseconds".to_owned())
+            })
+    }
+}
+
+impl From<Duration> for time::Duration {
+    fn from(duration: Duration) -> Self {
+        time::Duration::from_nanos(duration.nanoseconds)
+    }
+}
+
+impl FromStr for Duration {
+    type Err = SpudError;
+
+    /// Parses a string such as "1h30m", "90s" or "1m500ms" into a `Duration` instance.
+    ///
+    /// The string is a sequence of whole numbers, each followed by one of the units `h`, `m`, `s`, `ms`,
+    /// `us` or `ns`. Leading and trailing whitespace is ignored, whitespace inside the duration is rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rest: &str = s.trim();
+
+        if rest.is_empty() {
+            return Err(SpudError::ValidationError(
+                "Invalid duration format".to_owned(),
+            ));
+        }
+
+        let mut nanoseconds: u64 = 0;
+
+        while !rest.is_empty() {
+            let digits: usize = rest.find(|c: char| !c.is_ascii_digit()).ok_or_else(|| {
+                SpudError::ValidationError(format!("Missing unit after '{rest}'"))
+            })?;
+
+            let unit_len: usize = rest[digits..]
+                .find(|c: char| c.is_ascii_digit())
+                .unwrap_or(rest.len() - digits);
+
+            let (number, unit) = (&rest[..digits], &rest[digits..digits + unit_len]);
+
+            let value: u64 = u64::from_str(number).map_err(|_| {
+                SpudError::ValidationError(format!("Invalid duration amount '{number}'"))
+            })?;
+
+            let (_, scale) = UNITS
+                .iter()
+                .find(|(name, _)| *name == unit)
+                .ok_or_else(|| {
+                    SpudError::ValidationError(format!("Unknown duration unit '{unit}'"))
+                })?;
+
+            nanoseconds = value
+                .checked_mul(*scale)
+                .and_then(|value| nanoseconds.checked_add(value))
+                .ok_or_else(|| {
+                    SpudError::ValidationError("Duration exceeds u64::MAX nanoseconds".to_owned())
+                })?;
+
+            rest = &rest[digits + unit_len..];
+        }
+
+        Ok(Duration { nanoseconds })
+    }
+}
+
+impl fmt::Display for Duration {
+    /// Writes the duration in the format accepted by `from_str`, e.g. "1h30m", or "0s" when it is empty.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.nanoseconds == 0 {
+            return write!(f, "0s");
+        }
+
+        let mut rest: u64 = self.nanoseconds;
+
+        for (unit, scale) in UNITS {
+            if rest >= scale {
+                write!(f, "{}{unit}", rest / scale)?;
+
+                rest %= scale;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        let duration: Duration = "1h30m".parse().unwrap();
+
+        assert_eq!(duration.as_nanos(), 5_400 * NANOS_PER_SECOND);
+
+        let duration: Duration = " 1m500ms\n".parse().unwrap();
+
+        assert_eq!(duration.as_nanos(), 60_500_000_000);
+
+        let duration: Duration = "2s3us4ns".parse().unwrap();
+
+        assert_eq!(duration.as_nanos(), 2_000_003_004);
+    }
+
+    #[test]
+    fn test_parse_invalid_duration() {
+        assert!("".parse::<Duration>().is_err());
+        assert!("90".parse::<Duration>().is_err());
+        assert!("h".parse::<Duration>().is_err());
+        assert!("1d".parse::<Duration>().is_err());
+        assert!("1h 30m".parse::<Duration>().is_err());
+        assert!("-1s".parse::<Duration>().is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_overflow() {
+        let Err(SpudError::ValidationError(message)) = "5124096h".parse::<Duration>() else {
+            panic!("expected a validation error");
+        };
+
+        assert_eq!(message, "Duration exceeds u64::MAX nanoseconds");
+
+        assert!("18446744073709551615ns".parse::<Duration>().is_ok());
+        assert!("18446744073709551615ns1ns".parse::<Duration>().is_err());
+    }
+
+    #[test]
+    fn test_display_duration() {
+        assert_eq!(Duration::from_nanos(0).to_string(), "0s");
+        assert_eq!(
+            Duration::from_nanos(5_400 * NANOS_PER_SECOND).to_string(),
+            "1h30m"
+        );
+        assert_eq!(Duration::from_nanos(2_000_003_004).to_string(), "2s3us4ns");
+
+        let duration: Duration = Duration::from_nanos(u64::MAX);
+
+        assert_eq!(duration.to_string().parse::<Duration>().unwrap(), duration);
+    }
+
+    #[test]
+    fn test_duration_std_conversion() {
+        let duration: Duration = time::Duration::from_millis(1_500).try_into().unwrap();
+
+        assert_eq!(duration.as_nanos(), 1_500_000_000);
+        assert_eq!(
+            time::Duration::from(duration),
+            time::Duration::from_millis(1_500)
+        );
+
+        assert!(Duration::try_from(time::Duration::MAX).is_err());
+    }
+
+    #[test]
+    fn test_duration_as_le_bytes() {
+        let bytes: Vec<u8> = Duration::from_nanos(90_250_000_000).as_le_bytes();
+
+        assert_eq!(bytes.len(), 8 + 4);
+        assert_eq!(&bytes[..8], &90_i64.to_le_bytes());
+        assert_eq!(&bytes[8..], &250_000_000_u32.to_le_bytes());
+    }
+}
@@ -1,14 +1,30 @@
+#[cfg(feature = "std")]
 mod binary_blob;
+#[cfg(feature = "std")]
+mod capabilities;
 mod date;
 mod date_time;
+mod duration;
+#[cfg(feature = "std")]
 mod object_id;
+#[cfg(feature = "std")]
 mod spud_string;
 mod time;
+#[cfg(feature = "std")]
+mod uuid;
 
+#[cfg(feature = "std")]
 pub use binary_blob::BinaryBlob;
+#[cfg(feature = "std")]
+pub use capabilities::Capabilities;
 pub use date::Date;
 pub use date_time::DateTime;
-pub use object_id::ObjectId;
+pub use duration::Duration;
+#[cfg(feature = "std")]
+pub use object_id::{ObjectId, set_instance_identifier};
 pub use rust_decimal::Decimal;
+#[cfg(feature = "std")]
 pub use spud_string::SpudString;
 pub use time::Time;
+#[cfg(feature = "std")]
+pub use uuid::Uuid;
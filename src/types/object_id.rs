@@ -4,7 +4,7 @@ use core::{
 };
 use std::{
     fmt,
-    sync::LazyLock,
+    sync::{LazyLock, OnceLock},
     time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -22,13 +22,40 @@ use super::spud_string::SpudString;
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ObjectId([u8; 10]);
 
-static INSTANCE_IDENTIFIER: LazyLock<[u8; 3]> = LazyLock::new(|| {
-    let mut instance_bytes: [u8; 3] = [0u8; 3];
+static INSTANCE_IDENTIFIER: OnceLock<[u8; 3]> = OnceLock::new();
 
-    getrandom::fill(&mut instance_bytes).expect("Failed to generate instance identifier");
+fn instance_identifier() -> &'static [u8; 3] {
+    INSTANCE_IDENTIFIER.get_or_init(|| {
+        let mut instance_bytes: [u8; 3] = [0u8; 3];
 
-    instance_bytes
-});
+        getrandom::fill(&mut instance_bytes).expect("Failed to generate instance identifier");
+
+        instance_bytes
+    })
+}
+
+/// Sets the 3-byte instance identifier embedded in every `ObjectId` generated by this process.
+///
+/// By default the identifier is generated randomly the first time an `ObjectId` is created,
+/// setting it explicitly makes ids reproducible and avoids `getrandom` for the identifier.
+/// It has to be called once, before any `ObjectId` is generated.
+///
+/// # Examples
+///
+/// ```rust
+/// spud_rs::set_instance_identifier([0xAB, 0xCD, 0xEF]).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the identifier has already been set or generated.
+pub fn set_instance_identifier(identifier: [u8; 3]) -> Result<(), SpudError> {
+    INSTANCE_IDENTIFIER.set(identifier).map_err(|_| {
+        SpudError::ValidationError(
+            "The instance identifier has already been initialised".to_owned(),
+        )
+    })
+}
 
 static COUNTER_SEED: LazyLock<u32> = LazyLock::new(|| {
     let mut seed_bytes: [u8; 4] = [0u8; 4];
@@ -60,7 +87,7 @@ impl ObjectId {
         };
 
         id[0..4].copy_from_slice(&timestamp_secs.to_le_bytes());
-        id[4..7].copy_from_slice(&INSTANCE_IDENTIFIER[..]);
+        id[4..7].copy_from_slice(instance_identifier());
 
         let count_val: u32 = ID_COUNTER.fetch_add(1, Ordering::Relaxed);
         let counter_24bit: u32 = count_val & 0x00FF_FFFF;
@@ -76,6 +103,24 @@ impl ObjectId {
     pub fn as_bytes(&self) -> &[u8; 10] {
         &self.0
     }
+
+    #[must_use]
+    /// Returns the creation time of the `ObjectId`, in seconds since the UNIX epoch.
+    pub fn timestamp(&self) -> u32 {
+        u32::from_le_bytes([self.0[0], self.0[1], self.0[2], self.0[3]])
+    }
+
+    #[must_use]
+    /// Returns the 3-byte identifier of the process instance that created the `ObjectId`.
+    pub fn instance(&self) -> [u8; 3] {
+        [self.0[4], self.0[5], self.0[6]]
+    }
+
+    #[must_use]
+    /// Returns the 24-bit counter value of the `ObjectId`, which orders ids created within the same second.
+    pub fn counter(&self) -> u32 {
+        u32::from_le_bytes([self.0[7], self.0[8], self.0[9], 0])
+    }
 }
 
 impl Display for ObjectId {
@@ -120,6 +165,26 @@ impl From<[u8; 10]> for ObjectId {
     }
 }
 
+impl TryFrom<&[u8]> for ObjectId {
+    type Error = SpudError;
+
+    /// Builds an `ObjectId` from its raw bytes, e.g. read back from a decoded blob.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slice is not exactly 10 bytes long.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes: [u8; 10] = bytes.try_into().map_err(|_| {
+            SpudError::ValidationError(format!(
+                "Invalid ObjectId length: expected 10 bytes, got {}",
+                bytes.len()
+            ))
+        })?;
+
+        Ok(ObjectId(bytes))
+    }
+}
+
 impl TryFrom<SpudString> for ObjectId {
     type Error = SpudError;
 
@@ -198,6 +263,23 @@ mod tests {
         assert_eq!(id, from_bytes);
     }
 
+    #[test]
+    fn test_try_from_slice() {
+        let id: ObjectId = ObjectId::new().expect("Failed to create ObjectId");
+        let bytes: Vec<u8> = id.as_bytes().to_vec();
+
+        assert_eq!(ObjectId::try_from(bytes.as_slice()).unwrap(), id);
+    }
+
+    #[test]
+    fn test_try_from_slice_err() {
+        let too_short: Result<ObjectId, SpudError> = ObjectId::try_from(&[0u8; 9][..]);
+        let too_long: Result<ObjectId, SpudError> = ObjectId::try_from(&[0u8; 11][..]);
+
+        assert!(matches!(too_short, Err(SpudError::ValidationError(_))));
+        assert!(matches!(too_long, Err(SpudError::ValidationError(_))));
+    }
+
     #[test]
     fn test_try_from_spud_string() {
         let id: ObjectId = ObjectId::new().expect("Failed to create ObjectId");
@@ -216,6 +298,38 @@ mod tests {
         assert!(parsed_id.is_err());
     }
 
+    #[test]
+    fn test_timestamp() {
+        let before: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let id: ObjectId = ObjectId::new().expect("Failed to create ObjectId");
+
+        let after: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        assert!((before..=after).contains(&u64::from(id.timestamp())));
+    }
+
+    #[test]
+    fn test_components() {
+        let id: ObjectId =
+            ObjectId::from([0x78, 0x56, 0x34, 0x12, 0xAA, 0xBB, 0xCC, 0x03, 0x02, 0x01]);
+
+        assert_eq!(id.timestamp(), 0x1234_5678);
+        assert_eq!(id.instance(), [0xAA, 0xBB, 0xCC]);
+        assert_eq!(id.counter(), 0x01_0203);
+
+        let created: ObjectId = ObjectId::new().expect("Failed to create ObjectId");
+
+        assert_eq!(&created.instance(), instance_identifier());
+        assert!(created.counter() <= 0x00FF_FFFF);
+    }
+
     #[test]
     fn test_debug_impl() {
         let id: ObjectId = ObjectId::new().expect("Failed to create ObjectId");
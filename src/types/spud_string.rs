@@ -1,4 +1,8 @@
-use core::{fmt, ops::Deref};
+use core::{fmt, ops::Deref, str::Utf8Error};
+
+use std::{path::Path, string::FromUtf8Error};
+
+use crate::SpudError;
 
 use super::object_id::ObjectId;
 
@@ -8,6 +12,24 @@ use super::object_id::ObjectId;
 pub struct SpudString(Vec<u8>);
 
 impl SpudString {
+    /// Creates a `SpudString` from raw bytes, checking up front that they are valid UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, which still holds the bytes, if they are not valid UTF-8.
+    pub fn try_from_utf8(bytes: Vec<u8>) -> Result<Self, FromUtf8Error> {
+        String::from_utf8(bytes).map(Self::from)
+    }
+
+    /// Returns the string as a `&str`, unlike `Display` which replaces invalid sequences.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not valid UTF-8.
+    pub fn as_str(&self) -> Result<&str, Utf8Error> {
+        str::from_utf8(&self.0)
+    }
+
     #[must_use]
     /// Returns the length of the string in bytes.
     pub fn len(&self) -> usize {
@@ -31,6 +53,38 @@ impl SpudString {
     pub fn into_inner(self) -> Vec<u8> {
         self.0
     }
+
+    /// Returns a copy of the string with every character lowercased, following Unicode case mapping.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not valid UTF-8.
+    pub fn to_lowercase(&self) -> Result<Self, SpudError> {
+        Ok(Self::from(self.to_str()?.to_lowercase()))
+    }
+
+    /// Returns a copy of the string with every character uppercased, following Unicode case mapping.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not valid UTF-8.
+    pub fn to_uppercase(&self) -> Result<Self, SpudError> {
+        Ok(Self::from(self.to_str()?.to_uppercase()))
+    }
+
+    /// Returns a copy of the string without leading and trailing Unicode whitespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not valid UTF-8.
+    pub fn trim(&self) -> Result<Self, SpudError> {
+        Ok(Self::from(self.to_str()?.trim()))
+    }
+
+    fn to_str(&self) -> Result<&str, SpudError> {
+        self.as_str()
+            .map_err(|err| SpudError::EncodingError(format!("string is not valid UTF-8: {err}")))
+    }
 }
 
 impl From<&str> for SpudString {
@@ -51,6 +105,21 @@ impl From<&String> for SpudString {
     }
 }
 
+impl TryFrom<&Path> for SpudString {
+    type Error = SpudError;
+
+    /// Converts a path into its string form, failing instead of replacing non-UTF-8 sequences.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is not valid UTF-8.
+    fn try_from(value: &Path) -> Result<Self, Self::Error> {
+        value.to_str().map(Self::from).ok_or_else(|| {
+            SpudError::EncodingError(format!("path is not valid UTF-8: {}", value.display()))
+        })
+    }
+}
+
 impl AsRef<[u8]> for SpudString {
     fn as_ref(&self) -> &[u8] {
         &self.0
@@ -162,4 +231,77 @@ mod tests {
 
         assert_eq!(s.len(), 13); // "Hello, world!" is 13 bytes long
     }
+
+    #[test]
+    fn test_spud_string_try_from_path() {
+        let spud_string: SpudString =
+            SpudString::try_from(Path::new("/tmp/café/日本.spud")).unwrap();
+
+        assert_eq!(spud_string.as_bytes(), "/tmp/café/日本.spud".as_bytes());
+    }
+
+    #[test]
+    fn test_spud_string_case_conversion() {
+        let ascii: SpudString = SpudString::from("Hello, World!");
+
+        assert_eq!(ascii.to_lowercase().unwrap().as_bytes(), b"hello, world!");
+        assert_eq!(ascii.to_uppercase().unwrap().as_bytes(), b"HELLO, WORLD!");
+
+        let dotted: SpudString = SpudString::from("İstanbul");
+
+        assert_eq!(
+            dotted.to_lowercase().unwrap().as_bytes(),
+            "i\u{307}stanbul".as_bytes()
+        );
+        assert_eq!(
+            SpudString::from("straße")
+                .to_uppercase()
+                .unwrap()
+                .as_bytes(),
+            "STRASSE".as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_spud_string_trim() {
+        let padded: SpudString = SpudString::from("\t  Hello, world!\u{3000}\n");
+
+        assert_eq!(padded.trim().unwrap().as_bytes(), b"Hello, world!");
+        assert!(SpudString::from(" \r\n ").trim().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_spud_string_not_utf8() {
+        let invalid: SpudString = SpudString(vec![0x48, 0xFF, 0x49]);
+
+        assert!(invalid.to_lowercase().is_err());
+        assert!(invalid.to_uppercase().is_err());
+        assert!(invalid.trim().is_err());
+    }
+
+    #[test]
+    fn test_spud_string_as_str() {
+        let valid: SpudString = SpudString::try_from_utf8("café".as_bytes().to_vec()).unwrap();
+
+        assert_eq!(valid.as_str().unwrap(), "café");
+
+        let error: FromUtf8Error = SpudString::try_from_utf8(vec![0x48, 0xFF, 0x49]).unwrap_err();
+
+        assert_eq!(error.into_bytes(), [0x48, 0xFF, 0x49]);
+
+        let invalid: SpudString = SpudString(vec![0x48, 0xFF, 0x49]);
+
+        assert_eq!(invalid.as_str().unwrap_err().valid_up_to(), 1);
+        assert_eq!(invalid.to_string(), "H\u{FFFD}I");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spud_string_try_from_path_not_utf8() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let path: &Path = Path::new(OsStr::from_bytes(b"/tmp/\xFF.spud"));
+
+        assert!(SpudString::try_from(path).is_err());
+    }
 }
@@ -1,3 +1,4 @@
+use alloc::{borrow::ToOwned, vec::Vec};
 use core::{fmt, str::FromStr};
 
 use chrono::{NaiveDateTime, NaiveTime, Timelike};
@@ -106,8 +107,11 @@ impl FromStr for Time {
     type Err = SpudError;
 
     /// Parses a string in the format "HH:MM:SS" or "HH:MM:SS.NS" into a `Time` instance.
+    ///
+    /// `NS` is the nanosecond count as an integer, up to `999999999`. Leading and trailing
+    /// whitespace is ignored, whitespace inside the time is rejected.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts: Vec<&str> = s.split(':').collect();
+        let mut parts: Vec<&str> = s.trim().split(':').collect();
 
         if parts.len() != 3 {
             return Err(SpudError::ValidationError("Invalid time format".to_owned()));
@@ -199,6 +203,8 @@ impl fmt::Display for Time {
 
 #[cfg(test)]
 mod tests {
+    use alloc::string::ToString;
+
     use chrono::NaiveDate;
 
     use super::*;
@@ -286,6 +292,19 @@ mod tests {
         assert_eq!(time_no_ns.nanosecond, 0);
     }
 
+    #[test]
+    fn test_parse_time_padded() {
+        let time: Time = " 12:30:45\n".parse().unwrap();
+
+        assert_eq!(time.to_string(), "12:30:45");
+
+        let time_ns: Time = "\t12:30:45.500000000  ".parse().unwrap();
+
+        assert_eq!(time_ns.nanosecond, 500_000_000);
+
+        assert!("12: 30:45".parse::<Time>().is_err());
+    }
+
     #[test]
     fn test_parse_invalid_time() {
         assert!("25:00:00".parse::<Time>().is_err());
@@ -0,0 +1,112 @@
+use core::{fmt, str::FromStr};
+
+use crate::SpudError;
+
+/// A 128-bit universally unique identifier, stored as its 16 raw bytes.
+///
+/// It is displayed and parsed in the hyphenated form, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uuid([u8; 16]);
+
+impl Uuid {
+    #[must_use]
+    /// Creates a new `Uuid` from its 16 bytes, in the order they appear in the hyphenated form.
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    #[must_use]
+    /// Returns the 16 bytes of the `Uuid`.
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl From<[u8; 16]> for Uuid {
+    fn from(bytes: [u8; 16]) -> Self {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = SpudError;
+
+    /// Parses a hyphenated `Uuid`, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`, in either case.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not 32 hex digits grouped 8-4-4-4-12.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SpudError::ValidationError(format!("Invalid UUID: {s}"));
+
+        let groups: Vec<&str> = s.split('-').collect();
+
+        if groups
+            .iter()
+            .map(|group| group.len())
+            .collect::<Vec<usize>>()
+            != [8, 4, 4, 4, 12]
+        {
+            return Err(invalid());
+        }
+
+        let digits: String = groups.concat();
+
+        if !digits.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+
+        let mut bytes: [u8; 16] = [0; 16];
+
+        for (byte, pair) in bytes.iter_mut().zip(digits.as_bytes().chunks(2)) {
+            let pair: &str = core::str::from_utf8(pair).map_err(|_| invalid())?;
+
+            *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid())?;
+        }
+
+        Ok(Self(bytes))
+    }
+}
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                write!(f, "-")?;
+            }
+
+            write!(f, "{byte:02x}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid_display() {
+        let uuid: Uuid = Uuid::from_bytes([
+            0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f,
+            0xe0, 0xc8,
+        ]);
+
+        assert_eq!(uuid.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    }
+
+    #[test]
+    fn test_uuid_from_str() {
+        let uuid: Uuid = Uuid::from_str("67E55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+
+        assert_eq!(uuid.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    }
+
+    #[test]
+    fn test_uuid_from_str_invalid() {
+        assert!(Uuid::from_str("67e5504410b1426f9247bb680e5fe0c8").is_err());
+        assert!(Uuid::from_str("67e55044-10b1-426f-9247-bb680e5fe0cg").is_err());
+        assert!(Uuid::from_str("+7e55044-10b1-426f-9247-bb680e5fe0c8").is_err());
+    }
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "sync")]
+
+use spud_rs::{SpudBuilderSync, SpudDecoder, set_instance_identifier, types::ObjectId};
+
+#[test]
+fn test_set_instance_identifier() {
+    let identifier: [u8; 3] = [0xAB, 0xCD, 0xEF];
+
+    set_instance_identifier(identifier).unwrap();
+
+    assert!(set_instance_identifier([0, 0, 0]).is_err());
+
+    let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+    for _ in 0..2 {
+        builder.object(|_| Ok(())).unwrap();
+    }
+
+    let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+    let object_ids: Vec<ObjectId> = SpudDecoder::new(&encoded_bytes)
+        .unwrap()
+        .object_ids()
+        .unwrap();
+
+    assert_eq!(object_ids.len(), 2);
+
+    for object_id in object_ids {
+        assert_eq!(object_id.as_bytes()[4..7], identifier);
+    }
+}
@@ -0,0 +1,337 @@
+#![cfg(feature = "sync")]
+
+//! Encodes randomly generated objects and checks that decoding them gives back the values they were built from.
+//!
+//! The expected JSON is derived from the generated Rust values alone, never from the encoder, so any value the
+//! decoder renders differently from what was written fails the test along with the seed that produced it.
+
+use chrono::TimeDelta;
+use serde_json::{Map, Number, Value};
+use spud_rs::{
+    SpudBuilderSync, SpudDecoder, SpudError, SpudObjectSync,
+    types::{BinaryBlob, Date, DateTime, Decimal, Time, Uuid},
+};
+
+/// `SplitMix64`, enough to spread seeds over every generator branch without pulling in a rand dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z: u64 = self.0;
+
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn bytes<const N: usize>(&mut self) -> [u8; N] {
+        core::array::from_fn(|_| self.next_u64() as u8)
+    }
+}
+
+enum Generated {
+    Null,
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    F32(f32),
+    F64(f64),
+    Decimal(Decimal),
+    Char(char),
+    String(String),
+    Blob(Vec<u8>),
+    Date(u16, u8, u8),
+    Time(u8, u8, u8, u32),
+    DateTime((u16, u8, u8), (u8, u8, u8, u32)),
+    Duration(i64),
+    Uuid([u8; 16]),
+    Option(Option<u32>),
+    Array(Vec<i32>),
+    Object(Vec<(String, Generated)>),
+}
+
+const STRING_CHARS: &[char] = &['a', 'Z', '0', ' ', '"', '\\', '\n', 'é', '日', '🦀'];
+
+impl Generated {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn random(rng: &mut Rng, depth: usize) -> Self {
+        let variants: u64 = if depth < 2 { 26 } else { 25 };
+
+        match rng.below(variants) {
+            0 => Self::Null,
+            1 => Self::Bool(rng.below(2) == 1),
+            2 => Self::U8(rng.next_u64() as u8),
+            3 => Self::U16(rng.next_u64() as u16),
+            4 => Self::U32(rng.next_u64() as u32),
+            5 => Self::U64(rng.next_u64()),
+            6 => Self::U128(u128::from_le_bytes(rng.bytes())),
+            7 => Self::I8(rng.next_u64() as i8),
+            8 => Self::I16(rng.next_u64() as i16),
+            9 => Self::I32(rng.next_u64() as i32),
+            10 => Self::I64(rng.next_u64() as i64),
+            11 => Self::I128(i128::from_le_bytes(rng.bytes())),
+            12 => Self::F32(f32::from(rng.next_u64() as i16) / 64.0),
+            13 => Self::F64(f64::from(rng.next_u64() as i32) / 1024.0),
+            14 => Self::Decimal(Decimal::new(rng.next_u64() as i64, rng.below(29) as u32)),
+            15 => Self::Char(STRING_CHARS[rng.below(STRING_CHARS.len() as u64) as usize]),
+            16 => Self::String(
+                (0..rng.below(12))
+                    .map(|_| STRING_CHARS[rng.below(STRING_CHARS.len() as u64) as usize])
+                    .collect(),
+            ),
+            17 => Self::Blob((0..rng.below(300)).map(|_| rng.next_u64() as u8).collect()),
+            18 => {
+                let (year, month, day) = random_date(rng);
+
+                Self::Date(year, month, day)
+            }
+            19 => {
+                let (hour, minute, second, nanosecond) = random_time(rng);
+
+                Self::Time(hour, minute, second, nanosecond)
+            }
+            20 => Self::DateTime(random_date(rng), random_time(rng)),
+            21 => Self::Duration(rng.next_u64() as i64 >> 24),
+            22 => Self::Uuid(rng.bytes()),
+            23 => Self::Option((rng.below(2) == 1).then(|| rng.next_u64() as u32)),
+            24 => Self::Array((0..rng.below(6)).map(|_| rng.next_u64() as i32).collect()),
+            _ => Self::Object(random_fields(rng, depth + 1)),
+        }
+    }
+
+    fn write(&self, obj: &SpudObjectSync, name: &str) -> Result<(), SpudError> {
+        match self {
+            Self::Null => obj.add_value(name, ())?,
+            Self::Bool(value) => obj.add_value(name, *value)?,
+            Self::U8(value) => obj.add_value(name, *value)?,
+            Self::U16(value) => obj.add_value(name, *value)?,
+            Self::U32(value) => obj.add_value(name, *value)?,
+            Self::U64(value) => obj.add_value(name, *value)?,
+            Self::U128(value) => obj.add_value(name, *value)?,
+            Self::I8(value) => obj.add_value(name, *value)?,
+            Self::I16(value) => obj.add_value(name, *value)?,
+            Self::I32(value) => obj.add_value(name, *value)?,
+            Self::I64(value) => obj.add_value(name, *value)?,
+            Self::I128(value) => obj.add_value(name, *value)?,
+            Self::F32(value) => obj.add_value(name, *value)?,
+            Self::F64(value) => obj.add_value(name, *value)?,
+            Self::Decimal(value) => obj.add_value(name, *value)?,
+            Self::Char(value) => obj.add_value(name, *value)?,
+            Self::String(value) => obj.add_value(name, value.as_str())?,
+            Self::Blob(value) => obj.add_value(name, BinaryBlob::new(value))?,
+            Self::Date(year, month, day) => obj.add_value(name, Date::new(*year, *month, *day)?)?,
+            Self::Time(hour, minute, second, nanosecond) => {
+                obj.add_value(name, Time::new(*hour, *minute, *second, *nanosecond)?)?
+            }
+            Self::DateTime((year, month, day), (hour, minute, second, nanosecond)) => obj
+                .add_value(
+                    name,
+                    DateTime::new(
+                        Date::new(*year, *month, *day)?,
+                        Time::new(*hour, *minute, *second, *nanosecond)?,
+                    ),
+                )?,
+            Self::Duration(millis) => obj.add_value(name, TimeDelta::milliseconds(*millis))?,
+            Self::Uuid(bytes) => obj.add_value(name, Uuid::from_bytes(*bytes))?,
+            Self::Option(value) => obj.add_value(name, *value)?,
+            Self::Array(values) => obj.add_value(name, values.as_slice())?,
+            Self::Object(fields) => {
+                return obj.object(name, |nested: &SpudObjectSync| write_fields(nested, fields));
+            }
+        };
+
+        Ok(())
+    }
+
+    fn expected(&self) -> Value {
+        match self {
+            Self::Null | Self::Option(None) => Value::Null,
+            Self::Bool(value) => Value::Bool(*value),
+            Self::U8(value) => Value::from(*value),
+            Self::U16(value) => Value::from(*value),
+            Self::U32(value) | Self::Option(Some(value)) => Value::from(*value),
+            Self::U64(value) => Value::from(*value),
+            Self::U128(value) => Value::Number(Number::from(*value)),
+            Self::I8(value) => Value::from(*value),
+            Self::I16(value) => Value::from(*value),
+            Self::I32(value) => Value::from(*value),
+            Self::I64(value) => Value::from(*value),
+            Self::I128(value) => Value::Number(Number::from(*value)),
+            Self::F32(value) => Value::Number(Number::from_f64(f64::from(*value)).unwrap()),
+            Self::F64(value) => Value::Number(Number::from_f64(*value).unwrap()),
+            Self::Decimal(value) => Value::String(value.to_string()),
+            Self::Char(value) => Value::String(value.to_string()),
+            Self::String(value) => Value::String(value.clone()),
+            Self::Blob(value) => {
+                Value::Array(value.iter().map(|byte| Value::from(*byte)).collect())
+            }
+            Self::Date(year, month, day) => Value::String(format!("{year:04}-{month:02}-{day:02}")),
+            Self::Time(hour, minute, second, nanosecond) => {
+                Value::String(format_time(*hour, *minute, *second, *nanosecond))
+            }
+            Self::DateTime((year, month, day), (hour, minute, second, nanosecond)) => {
+                Value::String(format!(
+                    "{year:04}-{month:02}-{day:02} {}",
+                    format_time(*hour, *minute, *second, *nanosecond)
+                ))
+            }
+            Self::Duration(millis) => Value::String(format_duration(*millis)),
+            Self::Uuid(bytes) => Value::String(format_uuid(bytes)),
+            Self::Array(values) => {
+                Value::Array(values.iter().map(|value| Value::from(*value)).collect())
+            }
+            Self::Object(fields) => Value::Object(expected_fields(fields)),
+        }
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn random_date(rng: &mut Rng) -> (u16, u8, u8) {
+    (
+        rng.below(10_000) as u16,
+        rng.below(12) as u8 + 1,
+        rng.below(28) as u8 + 1,
+    )
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn random_time(rng: &mut Rng) -> (u8, u8, u8, u32) {
+    let nanosecond: u32 = if rng.below(2) == 0 {
+        0
+    } else {
+        rng.below(1_000_000_000) as u32
+    };
+
+    (
+        rng.below(24) as u8,
+        rng.below(60) as u8,
+        rng.below(60) as u8,
+        nanosecond,
+    )
+}
+
+fn random_fields(rng: &mut Rng, depth: usize) -> Vec<(String, Generated)> {
+    (0..rng.below(8))
+        .map(|i| (format!("field_{i}"), Generated::random(rng, depth)))
+        .collect()
+}
+
+fn write_fields(obj: &SpudObjectSync, fields: &[(String, Generated)]) -> Result<(), SpudError> {
+    for (name, value) in fields {
+        value.write(obj, name)?;
+    }
+
+    Ok(())
+}
+
+fn expected_fields(fields: &[(String, Generated)]) -> Map<String, Value> {
+    fields
+        .iter()
+        .map(|(name, value)| (name.clone(), value.expected()))
+        .collect()
+}
+
+fn format_time(hour: u8, minute: u8, second: u8, nanosecond: u32) -> String {
+    if nanosecond == 0 {
+        format!("{hour:02}:{minute:02}:{second:02}")
+    } else {
+        format!("{hour:02}:{minute:02}:{second:02}.{nanosecond:09}")
+    }
+}
+
+/// Renders milliseconds as an ISO 8601 duration in seconds, e.g. `-PT1.5S`, or `P0D` when zero.
+fn format_duration(millis: i64) -> String {
+    if millis == 0 {
+        return "P0D".to_owned();
+    }
+
+    let sign: &str = if millis < 0 { "-" } else { "" };
+    let millis: u64 = millis.unsigned_abs();
+
+    let fraction: String = format!(".{:03}", millis % 1_000);
+
+    format!(
+        "{sign}PT{}{}S",
+        millis / 1_000,
+        fraction.trim_end_matches('0').trim_end_matches('.')
+    )
+}
+
+fn format_uuid(bytes: &[u8; 16]) -> String {
+    let hex: String = bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<Vec<String>>()
+        .concat();
+
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Drops the `oid` every decoded object carries, which the generated values know nothing about.
+fn strip_oids(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            fields.shift_remove("oid");
+            fields.values_mut().for_each(strip_oids);
+        }
+        Value::Array(values) => values.iter_mut().for_each(strip_oids),
+        _ => {}
+    }
+}
+
+#[test]
+fn test_roundtrip_random_objects() {
+    for seed in 0..256 {
+        let mut rng: Rng = Rng(seed);
+
+        let objects: Vec<Vec<(String, Generated)>> = (0..=rng.below(3))
+            .map(|_| random_fields(&mut rng, 0))
+            .collect();
+
+        let builder: SpudBuilderSync = SpudBuilderSync::new();
+
+        for fields in &objects {
+            builder
+                .object(|obj: &SpudObjectSync| write_fields(obj, fields))
+                .unwrap_or_else(|err| panic!("seed {seed}: encoding failed: {err}"));
+        }
+
+        let encoded_bytes: Vec<u8> = builder.encode().unwrap();
+
+        let mut decoded: Vec<Value> = SpudDecoder::new(&encoded_bytes)
+            .and_then(|decoder: SpudDecoder| decoder.decode_values())
+            .unwrap_or_else(|err| panic!("seed {seed}: decoding failed: {err}"));
+
+        decoded.iter_mut().for_each(strip_oids);
+
+        let expected: Vec<Value> = objects
+            .iter()
+            .map(|fields| Value::Object(expected_fields(fields)))
+            .collect();
+
+        assert_eq!(decoded, expected, "seed {seed}");
+    }
+}